@@ -0,0 +1,186 @@
+//! Record requests and responses to an archive directory, and replay them later.
+//!
+//! An archive directory has response bodies in numbered files like `0001.html`,
+//! and `index.jsonl`, which lists URLs, statuses and body files of the responses
+//! in order, one JSON object per line.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use reqwest::Url;
+use serde_json;
+
+use errors::*;
+use transport::{Response, Transport};
+
+const INDEX_FILE_NAME: &str = "index.jsonl";
+
+/// A response recorded in an archive.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    pub url: String,
+    /// HTTP status code, like 200.
+    pub status: u16,
+    /// Name of the file of the response body, relative to the archive directory.
+    pub file: String,
+}
+
+/// `Transport` recording every response of `inner` to an archive directory.
+pub struct RecordingTransport<T> {
+    inner: T,
+    dir: PathBuf,
+    count: Mutex<u32>,
+}
+
+impl<T: Transport> RecordingTransport<T> {
+    /// Create new `RecordingTransport` writing to `dir`, created if missing.
+    /// An archive already in `dir` is overwritten.
+    pub fn new<P: Into<PathBuf>>(inner: T, dir: P) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        File::create(dir.join(INDEX_FILE_NAME))?;
+
+        Ok(Self {
+            inner,
+            dir,
+            count: Mutex::new(0),
+        })
+    }
+}
+
+impl<T: Transport> Transport for RecordingTransport<T> {
+    fn get(&self, url: &Url) -> Result<Response> {
+        let response = self.inner.get(url)?;
+
+        // Hold the lock while writing, to keep the index in order.
+        let mut count = self.count.lock().unwrap();
+        *count += 1;
+
+        let entry = ArchiveEntry {
+            url: url.to_string(),
+            status: response.status,
+            file: format!("{:04}.html", *count),
+        };
+        File::create(self.dir.join(&entry.file))?.write_all(response.body.as_bytes())?;
+
+        let mut index = OpenOptions::new()
+            .append(true)
+            .open(self.dir.join(INDEX_FILE_NAME))?;
+        writeln!(index, "{}", serde_json::to_string(&entry)?)?;
+
+        Ok(response)
+    }
+}
+
+/// `Transport` serving responses recorded in an archive directory, in the recorded order.
+/// A request to a URL other than the next recorded one fails with `ErrorKind::ReplayMismatch`.
+pub struct ReplayTransport {
+    dir: PathBuf,
+    entries: Vec<ArchiveEntry>,
+    next: Mutex<usize>,
+}
+
+impl ReplayTransport {
+    /// Load an archive in `dir`.
+    pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref().to_owned();
+        let index = BufReader::new(File::open(dir.join(INDEX_FILE_NAME))?);
+
+        let mut entries = Vec::new();
+        for line in index.lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                entries.push(serde_json::from_str(&line)?);
+            }
+        }
+
+        Ok(Self {
+            dir,
+            entries,
+            next: Mutex::new(0),
+        })
+    }
+
+    /// Recorded responses, in order.
+    pub fn entries(&self) -> &[ArchiveEntry] {
+        &self.entries
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn get(&self, url: &Url) -> Result<Response> {
+        let mut next = self.next.lock().unwrap();
+
+        let entry = match self.entries.get(*next) {
+            Some(entry) if entry.url == url.as_str() => entry,
+            _ => return Err(ErrorKind::ReplayMismatch(url.to_string()).into()),
+        };
+        *next += 1;
+
+        let mut body = String::new();
+        File::open(self.dir.join(&entry.file))?.read_to_string(&mut body)?;
+
+        Ok(Response {
+            status: entry.status,
+            body,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_util::TestDir;
+    use transport::MemoryTransport;
+
+    #[test]
+    fn record_replay_test() {
+        let dir = TestDir::new("archive");
+
+        let foo_url = Url::parse("https://scholar.google.com/scholar?q=foo").unwrap();
+        let bar_url = Url::parse("https://scholar.google.com/scholar?q=bar").unwrap();
+
+        {
+            let mut inner = MemoryTransport::new();
+            inner.insert(
+                &foo_url,
+                Response {
+                    status: 503,
+                    body: String::new(),
+                },
+            );
+            inner.insert(&foo_url, Response::ok("foo"));
+            inner.insert(&bar_url, Response::ok("bar"));
+
+            let recorder = RecordingTransport::new(inner, &*dir).unwrap();
+            recorder.get(&foo_url).unwrap();
+            recorder.get(&foo_url).unwrap();
+            recorder.get(&bar_url).unwrap();
+        }
+
+        let replayer = ReplayTransport::load(&*dir).unwrap();
+        assert_eq!(
+            replayer.entries()[2],
+            ArchiveEntry {
+                url: String::from("https://scholar.google.com/scholar?q=bar"),
+                status: 200,
+                file: String::from("0003.html"),
+            }
+        );
+
+        // Out of order.
+        match replayer.get(&bar_url) {
+            Err(Error(ErrorKind::ReplayMismatch(_), _)) => {}
+            _ => panic!("not mismatched"),
+        }
+
+        assert_eq!(replayer.get(&foo_url).unwrap().status, 503);
+        assert_eq!(replayer.get(&foo_url).unwrap(), Response::ok("foo"));
+        assert_eq!(replayer.get(&bar_url).unwrap(), Response::ok("bar"));
+
+        // Exhausted.
+        assert!(replayer.get(&bar_url).is_err());
+    }
+}
@@ -0,0 +1,173 @@
+//! `AuthorProfile` and `AuthorSummary` structs, scraped from Google Scholar profile pages.
+
+use std::fmt;
+
+use paper::option_na;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AuthorProfile {
+    pub name: String,
+    pub affiliation: Option<String>,
+    /// Research interests listed by the author.
+    pub interests: Vec<String>,
+    /// Domain of the verified email address, like "example.edu".
+    pub email_domain: Option<String>,
+    /// Citation metrics table.
+    pub metrics: Option<CitationMetrics>,
+    /// Number of citations per year, in ascending order of year.
+    pub citation_histogram: Vec<YearlyCitations>,
+    /// Publications, possibly only some of them.
+    pub publications: Vec<Publication>,
+}
+
+/// Citation metrics of an author, for all time and for recent years.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CitationMetrics {
+    /// First year of the recent years, e.g. 2013 for "Since 2013".
+    pub since_year: Option<u32>,
+    pub citations: MetricValues,
+    pub h_index: MetricValues,
+    pub i10_index: MetricValues,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MetricValues {
+    pub all: u32,
+    pub recent: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct YearlyCitations {
+    pub year: u32,
+    pub count: u32,
+}
+
+/// One of publications listed in an author profile.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Publication {
+    pub title: String,
+    /// Link to the publication detail page of Google Scholar.
+    pub link: Option<String>,
+    /// Authors, as shown in the profile.
+    pub authors: Option<String>,
+    /// Journal, conference, etc.
+    pub venue: Option<String>,
+    /// Published year.
+    pub year: Option<u32>,
+    /// Cluster ID of the publication, if it is cited.
+    /// Publications merged from several clusters have the first one.
+    pub cluster_id: Option<u64>,
+    pub citation_count: u32,
+}
+
+/// An author listed in author search results.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AuthorSummary {
+    /// User ID of Google Scholar profile.
+    pub user_id: String,
+    pub name: String,
+    pub affiliation: Option<String>,
+    /// Total number of citations.
+    pub citation_count: Option<u32>,
+    /// Research interests listed by the author.
+    pub interests: Vec<String>,
+}
+
+impl fmt::Display for AuthorProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let interests = if self.interests.is_empty() {
+            None
+        } else {
+            Some(self.interests.join(", "))
+        };
+
+        write!(
+            f,
+            r#"{}
+ Affiliation: {}
+Email domain: {}
+   Interests: {}"#,
+            self.name,
+            option_na(&self.affiliation),
+            option_na(&self.email_domain),
+            option_na(&interests),
+        )?;
+
+        if let Some(ref m) = self.metrics {
+            let since = match m.since_year {
+                Some(year) => format!("Since {}", year),
+                None => String::from("Recent"),
+            };
+
+            write!(
+                f,
+                r#"
+{:>12} {:>10} {:>10}
+   Citations {:>10} {:>10}
+     h-index {:>10} {:>10}
+   i10-index {:>10} {:>10}"#,
+                "",
+                "All",
+                since,
+                m.citations.all,
+                m.citations.recent,
+                m.h_index.all,
+                m.h_index.recent,
+                m.i10_index.all,
+                m.i10_index.recent,
+            )?;
+        }
+
+        for c in &self.citation_histogram {
+            write!(f, "\n{:>12}: {}", c.year, c.count)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Publication {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            r#""{}"
+ Link to paper: {}
+       Authors: {}
+         Venue: {}
+Published year: {}
+    Cluster ID: {}
+Citation count: {}"#,
+            self.title,
+            option_na(&self.link),
+            option_na(&self.authors),
+            option_na(&self.venue),
+            option_na(&self.year),
+            option_na(&self.cluster_id),
+            self.citation_count,
+        )
+    }
+}
+
+impl fmt::Display for AuthorSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let interests = if self.interests.is_empty() {
+            None
+        } else {
+            Some(self.interests.join(", "))
+        };
+
+        write!(
+            f,
+            r#"{}
+       User ID: {}
+   Affiliation: {}
+Citation count: {}
+     Interests: {}"#,
+            self.name,
+            self.user_id,
+            option_na(&self.affiliation),
+            option_na(&self.citation_count),
+            option_na(&interests),
+        )
+    }
+}
@@ -1,10 +1,28 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
 use clap::ArgMatches;
 
+use scholar::cache::CacheConfig;
+use scholar::language::Language;
+use scholar::rate_limit::RateLimit;
+use scholar::request::{BaseUrl, Collection, Jurisdiction, SortOrder};
+use scholar::retry::{BlockedAction, RetryPolicy};
+
 #[derive(Clone)]
 pub struct Config {
     pub max_result_count: Option<u32>,
     pub recursive_depth: u32,
     pub output_format: OutputFormat,
+    pub skip_citation_only: bool,
+    pub sort_order: SortOrder,
+    pub collection: Collection,
+    pub rate_limit: RateLimit,
+    pub retry: RetryPolicy,
+    pub cache: Option<CacheConfig>,
+    pub archive: Option<ArchiveMode>,
+    pub base_url: BaseUrl,
+    pub language: Language,
     pub verbose: bool,
 }
 
@@ -12,6 +30,21 @@ pub struct Config {
 pub enum OutputFormat {
     HumanReadable,
     Json,
+    Exported(ExportFormat),
+}
+
+/// Bibliography formats, in which citers found recursively are listed together.
+#[derive(Clone, Copy)]
+pub enum ExportFormat {
+    Bibtex,
+    Ris,
+    CslJson,
+}
+
+#[derive(Clone)]
+pub enum ArchiveMode {
+    Record(PathBuf),
+    Replay(PathBuf),
 }
 
 impl Config {
@@ -23,16 +56,105 @@ impl Config {
             super::MAX_RECURSIVE_DEPTH,
         );
 
-        let output_format = if recursive_depth > 0 || matches.is_present("json") {
-            OutputFormat::Json
+        let output_format = match matches.value_of("format") {
+            Some("bibtex") => OutputFormat::Exported(ExportFormat::Bibtex),
+            Some("ris") => OutputFormat::Exported(ExportFormat::Ris),
+            Some("csl-json") => OutputFormat::Exported(ExportFormat::CslJson),
+            _ if recursive_depth > 0 || matches.is_present("json") => OutputFormat::Json,
+            _ => OutputFormat::HumanReadable,
+        };
+
+        let collection = if matches.is_present("case-law") {
+            // Validated in app(). Without court IDs, all courts are searched.
+            let court_ids = values_t!(matches, "case-law", u32).unwrap_or_default();
+            if court_ids.is_empty() {
+                Collection::CaseLaw(Jurisdiction::AllCourts)
+            } else {
+                Collection::CaseLaw(Jurisdiction::Courts(court_ids))
+            }
         } else {
-            OutputFormat::HumanReadable
+            Collection::Articles {
+                include_patents: !matches.is_present("no-patents"),
+            }
+        };
+
+        let archive = matches
+            .value_of("record")
+            .map(|dir| ArchiveMode::Record(PathBuf::from(dir)))
+            .or_else(|| {
+                matches
+                    .value_of("replay")
+                    .map(|dir| ArchiveMode::Replay(PathBuf::from(dir)))
+            });
+        let replay = matches.is_present("replay"); // conflicting with --record
+
+        let rate_limit = {
+            let mut r = RateLimit::default();
+            if let Ok(delay) = value_t!(matches, "delay", f64) {
+                // Randomize the interval by up to 50% more.
+                let millis = (delay * 1000.0) as u64;
+                r.min_interval = Duration::from_millis(millis);
+                r.jitter = Duration::from_millis(millis / 2);
+            }
+            r.max_requests = value_t!(matches, "max-requests", u32).ok();
+            if replay {
+                r.min_interval = Duration::from_secs(0);
+                r.jitter = Duration::from_secs(0);
+            }
+            r
         };
 
+        let retry = {
+            let mut r = RetryPolicy::default();
+            if let Ok(retries) = value_t!(matches, "retries", u32) {
+                r.max_retries = retries;
+            }
+            if let Ok(wait) = value_t!(matches, "wait-if-blocked", u64) {
+                r.on_blocked = BlockedAction::Wait(Duration::from_secs(wait));
+            }
+            if replay {
+                // Retry recorded responses without waiting.
+                r.initial_backoff = Duration::from_secs(0);
+                if let BlockedAction::Wait(_) = r.on_blocked {
+                    r.on_blocked = BlockedAction::Wait(Duration::from_secs(0));
+                }
+            }
+            r
+        };
+
+        // Responses are cached only in a directory given explicitly.
+        let cache = matches.value_of("cache-dir").map(|dir| {
+            let mut c = CacheConfig::new(dir);
+            if let Ok(ttl) = value_t!(matches, "cache-ttl", u64) {
+                c.ttl = Some(Duration::from_secs(ttl));
+            }
+            c.offline = matches.is_present("offline");
+            c
+        });
+
         Self {
             max_result_count: value_t!(matches, "count", u32).ok(),
             recursive_depth,
             output_format,
+            skip_citation_only: matches.is_present("skip-citation-only"),
+            sort_order: if matches.is_present("sort-by-date") {
+                SortOrder::Date
+            } else {
+                SortOrder::Relevance
+            },
+            collection,
+            rate_limit,
+            retry,
+            cache,
+            archive,
+            base_url: matches
+                .value_of("base-url")
+                .map(|url| BaseUrl::new(url).unwrap()) // validated in app()
+                .unwrap_or_default(),
+            language: matches
+                .value_of("language")
+                .and_then(Language::from_code) // validated in app()
+                .unwrap_or_default(),
             verbose: matches.is_present("verbose"),
         }
     }
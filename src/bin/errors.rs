@@ -8,10 +8,4 @@ error_chain! {
     links {
         Scholar(::scholar::errors::Error, ::scholar::errors::ErrorKind);
     }
-
-    errors {
-        Blocked {
-            description("Request blocked")
-        }
-    }
 }
@@ -10,15 +10,27 @@ use std::fs;
 
 use clap::{App, Arg, ArgGroup, ArgMatches};
 
-use scholar::MAX_RESULT_COUNT;
-use scholar::request::{send_request, ClusterQuery, SearchQuery};
-use scholar::scrape::{CitationDocument, ClusterDocument, SearchDocument};
+use scholar::MAX_TOTAL_RESULT_COUNT;
+use scholar::archive::{RecordingTransport, ReplayTransport};
+use scholar::cache::DiskCache;
+use scholar::client::{ClientConfig, ScholarClient};
+use scholar::request::{AuthorProfileQuery, AuthorSearchQuery, BaseUrl, CitationQuery,
+                       ClusterQuery, RelatedQuery, SearchQuery};
+use scholar::scrape::{CitationDocument, SearchDocument};
+use scholar::transport::ReqwestTransport;
 
 mod config;
 mod errors;
 mod scrape;
+// Shared with tests of the library, which refer to its modules from the crate root.
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../test_util.rs"]
+mod test_util;
+#[cfg(test)]
+use scholar::{client, rate_limit, retry, transport};
 
-use config::Config;
+use config::{ArchiveMode, Config};
 use errors::*;
 
 const MAX_RECURSIVE_DEPTH: u32 = 5;
@@ -38,51 +50,154 @@ fn run() -> Result<()> {
 
     let cfg = Config::new(&matches);
 
+    if matches.is_present("clear-cache") {
+        let cache_cfg = cfg.cache.unwrap(); // requiring --cache-dir
+        let count = DiskCache::new(&cache_cfg.dir, cache_cfg.ttl).clear_stale()?;
+        println!(
+            "Removed {} expired responses from {}",
+            count,
+            cache_cfg.dir.display()
+        );
+
+        return Ok(());
+    }
+
+    let client_cfg = ClientConfig {
+        rate_limit: cfg.rate_limit.clone(),
+        retry: cfg.retry.clone(),
+        cache: cfg.cache.clone(),
+        base_url: cfg.base_url.clone(),
+        language: cfg.language,
+        verbose: cfg.verbose,
+        ..ClientConfig::default()
+    };
+    let mut client = match cfg.archive {
+        Some(ArchiveMode::Record(ref dir)) => {
+            let transport = RecordingTransport::new(ReqwestTransport::new(&client_cfg)?, dir.clone())?;
+            ScholarClient::with_transport(client_cfg, transport)
+        }
+        Some(ArchiveMode::Replay(ref dir)) => {
+            ScholarClient::with_transport(client_cfg, ReplayTransport::load(dir)?)
+        }
+        None => ScholarClient::new(client_cfg)?,
+    };
+    client.set_retry_observer(|e| {
+        eprintln!(
+            "Retry #{} in {} seconds ({}): {}",
+            e.retry,
+            e.wait.as_secs(),
+            e.reason,
+            e.url
+        );
+    });
+
     if matches.is_present("cluster-id") {
         let cluster_id = value_t!(matches, "cluster-id", u64).unwrap(); // validated in app()
         let query = ClusterQuery::new(cluster_id);
-        let body = send_request(&query, cfg.verbose)?;
-        let doc = ClusterDocument::from(&*body);
-        scrape::scrape_cluster_doc(&doc, &cfg)?;
+        scrape::search_cluster(&client, &query, &cfg)?;
 
         return Ok(());
     }
 
-    if let Some(cite_file) = matches.value_of("cite-html") {
-        let file = fs::File::open(cite_file)?;
-        let doc = CitationDocument::from_read(file)?;
-        scrape::scrape_citaiton_doc(&doc, &cfg)?;
+    if matches.is_present("related") {
+        let cluster_id = value_t!(matches, "related", u64).unwrap(); // validated in app()
+        let query = RelatedQuery::new(cluster_id);
+        scrape::search_papers(&client, &query, &cfg)?;
 
         return Ok(());
     }
 
-    let search_doc = if let Some(search_file) = matches.value_of("search-html") {
-        let file = fs::File::open(search_file)?;
-        SearchDocument::from_read(file)?
-    } else {
-        let mut query = SearchQuery::default();
+    if matches.is_present("cites") {
+        let cluster_id = value_t!(matches, "cites", u64).unwrap(); // validated in app()
+        let mut query = CitationQuery::from_cluster_id(cluster_id, &cfg.base_url);
 
-        if let Some(count) = cfg.max_result_count {
-            query.set_count(count);
-        }
         if let Some(words) = matches.value_of("words") {
             query.set_words(words);
         }
         if let Some(phrase) = matches.value_of("phrase") {
-            query.set_phrase(phrase);
+            query.append_phrase(phrase);
         }
         if let Some(authors) = matches.value_of("authors") {
             query.set_authors(authors);
         }
-        if matches.is_present("title-only") {
-            query.set_title_only(true);
+        if let Ok(year_from) = value_t!(matches, "year-from", u32) {
+            query.set_year_from(year_from);
+        }
+        if let Ok(year_to) = value_t!(matches, "year-to", u32) {
+            query.set_year_to(year_to);
         }
+        query.set_sort_order(cfg.sort_order);
 
-        let body = send_request(&query, cfg.verbose)?;
-        SearchDocument::from(&*body)
-    };
+        scrape::search_citations(&client, &query, &cfg)?;
+
+        return Ok(());
+    }
 
-    scrape::scrape_search_doc(&search_doc, &cfg)?;
+    if let Some(user_id) = matches.value_of("author-profile") {
+        let query = AuthorProfileQuery::new(user_id);
+        scrape::author_profile(&client, &query, &cfg)?;
+
+        return Ok(());
+    }
+
+    if let Some(name) = matches.value_of("find-author") {
+        let query = AuthorSearchQuery::new(name);
+        scrape::search_authors(&client, &query, &cfg)?;
+
+        return Ok(());
+    }
+
+    if let Some(cite_file) = matches.value_of("cite-html") {
+        let file = fs::File::open(cite_file)?;
+        let doc = CitationDocument::from_read(file)?
+            .with_base_url(&cfg.base_url)
+            .with_language(cfg.language);
+        scrape::scrape_citaiton_doc(&client, &doc, &cfg)?;
+
+        return Ok(());
+    }
+
+    if let Some(search_file) = matches.value_of("search-html") {
+        let file = fs::File::open(search_file)?;
+        let doc = SearchDocument::from_read(file)?
+            .with_base_url(&cfg.base_url)
+            .with_language(cfg.language);
+        scrape::scrape_search_doc(&client, &doc, &cfg)?;
+
+        return Ok(());
+    }
+
+    let mut query = SearchQuery::default();
+
+    if let Some(words) = matches.value_of("words") {
+        query.set_words(words);
+    }
+    if let Some(phrase) = matches.value_of("phrase") {
+        query.append_phrase(phrase);
+    }
+    if let Some(authors) = matches.value_of("authors") {
+        query.set_authors(authors);
+    }
+    if let Some(excluded_words) = matches.value_of("without") {
+        query.set_excluded_words(excluded_words);
+    }
+    if let Some(publication) = matches.value_of("published-in") {
+        query.set_publication(publication);
+    }
+    if matches.is_present("title-only") {
+        query.set_title_only(true);
+    }
+    if let Ok(year_from) = value_t!(matches, "year-from", u32) {
+        query.set_year_from(year_from);
+    }
+    if let Ok(year_to) = value_t!(matches, "year-to", u32) {
+        query.set_year_to(year_to);
+    }
+    query.set_collection(cfg.collection.clone());
+    query.set_include_citations(!matches.is_present("no-citations"));
+    query.set_sort_order(cfg.sort_order);
+
+    scrape::search_papers(&client, &query, &cfg)?;
 
     Ok(())
 }
@@ -97,9 +212,9 @@ fn app() -> App<'static, 'static> {
                 .help("Maximum number of search results (default = 5)")
                 .takes_value(true)
                 .validator(|v| match v.parse::<u32>() {
-                    Ok(v) if v > MAX_RESULT_COUNT => Err(format!(
+                    Ok(v) if v > MAX_TOTAL_RESULT_COUNT => Err(format!(
                         "The value is too large; exceeding {}",
-                        MAX_RESULT_COUNT
+                        MAX_TOTAL_RESULT_COUNT
                     )),
                     Ok(v) if v > 0 => Ok(()),
                     _ => Err(String::from("The value is not a positive integer")),
@@ -130,6 +245,20 @@ fn app() -> App<'static, 'static> {
                 .takes_value(true)
                 .display_order(3),
         )
+        .arg(
+            Arg::with_name("without")
+                .long("without")
+                .help("Search papers without these words")
+                .takes_value(true)
+                .display_order(4),
+        )
+        .arg(
+            Arg::with_name("published-in")
+                .long("published-in")
+                .help("Search papers published in this journal, conference, etc.")
+                .takes_value(true)
+                .display_order(5),
+        )
         .arg(
             Arg::with_name("title-only")
                 .short("t")
@@ -138,13 +267,94 @@ fn app() -> App<'static, 'static> {
                     "Search only papers which contain specified words in their title \
                      (default = false)",
                 )
-                .display_order(4),
+                .display_order(6),
+        )
+        .arg(
+            Arg::with_name("year-from")
+                .long("year-from")
+                .help("Search papers published in or after this year")
+                .takes_value(true)
+                .validator(validate_year)
+                .display_order(7),
+        )
+        .arg(
+            Arg::with_name("year-to")
+                .long("year-to")
+                .help("Search papers published in or before this year")
+                .takes_value(true)
+                .validator(validate_year)
+                .display_order(8),
+        )
+        .arg(
+            Arg::with_name("no-patents")
+                .long("no-patents")
+                .help("Search articles excluding patents (default = including patents)")
+                .conflicts_with_all(&[
+                    "cluster-id",
+                    "related",
+                    "cites",
+                    "author-profile",
+                    "find-author",
+                    "html",
+                ])
+                .display_order(9),
+        )
+        .arg(
+            Arg::with_name("no-citations")
+                .long("no-citations")
+                .help(
+                    "Let Google Scholar exclude [CITATION] entries from search results, \
+                     filling pages with other papers (default = including them)",
+                )
+                .conflicts_with_all(&[
+                    "cluster-id",
+                    "related",
+                    "cites",
+                    "author-profile",
+                    "find-author",
+                    "html",
+                ])
+                .display_order(10),
+        )
+        .arg(
+            Arg::with_name("case-law")
+                .long("case-law")
+                .help(
+                    "Search case law of US courts instead of articles, \
+                     only of the courts with these IDs if specified",
+                )
+                .value_name("court-ids")
+                .min_values(0)
+                .use_delimiter(true)
+                .validator(|v| match v.parse::<u32>() {
+                    Ok(_) => Ok(()),
+                    _ => Err(String::from("The value is not a court ID")),
+                })
+                .conflicts_with("no-patents")
+                .conflicts_with_all(&[
+                    "cluster-id",
+                    "related",
+                    "cites",
+                    "author-profile",
+                    "find-author",
+                    "html",
+                ])
+                .display_order(11),
+        )
+        .arg(
+            Arg::with_name("sort-by-date")
+                .long("sort-by-date")
+                .help(
+                    "Sort search results and citers by date, newest first, \
+                     listing only papers added recently (default = by relevance)",
+                )
+                .display_order(12),
         )
         .group(
             ArgGroup::with_name("search-query")
-                .args(&["words", "phrase", "authors"])
+                .args(&["words", "phrase", "authors", "published-in"])
                 .multiple(true)
-                .conflicts_with_all(&["cluster-id", "html"]),
+                .conflicts_with_all(&["cluster-id", "related", "html"]),
         )
         .arg(
             Arg::with_name("cluster-id")
@@ -155,8 +365,85 @@ fn app() -> App<'static, 'static> {
                     Ok(_) => Ok(()),
                     _ => Err(String::from("The value is not an integer")),
                 })
+                .conflicts_with_all(&["related", "html"])
+                .display_order(13),
+        )
+        .arg(
+            Arg::with_name("related")
+                .long("related")
+                .help("Search papers related to a paper with this cluster ID")
+                .value_name("cluster-id")
+                .validator(|v| match v.parse::<u64>() {
+                    Ok(_) => Ok(()),
+                    _ => Err(String::from("The value is not an integer")),
+                })
                 .conflicts_with("html")
-                .display_order(10),
+                .display_order(14),
+        )
+        .arg(
+            Arg::with_name("cites")
+                .long("cites")
+                .help(
+                    "Search papers citing a paper with this cluster ID, \
+                     filtered by --words, --phrase, --authors, --year-from and --year-to \
+                     if specified",
+                )
+                .value_name("cluster-id")
+                .validator(|v| match v.parse::<u64>() {
+                    Ok(_) => Ok(()),
+                    _ => Err(String::from("The value is not an integer")),
+                })
+                .conflicts_with_all(&[
+                    "cluster-id",
+                    "related",
+                    "html",
+                    "without",
+                    "published-in",
+                    "title-only",
+                ])
+                .display_order(15),
+        )
+        .arg(
+            Arg::with_name("author-profile")
+                .long("author-profile")
+                .help(
+                    "Get a profile of an author with this user ID, \
+                     listing publications up to --count",
+                )
+                .value_name("user-id")
+                .validator(|v| {
+                    if !v.is_empty() && v.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+                    {
+                        Ok(())
+                    } else {
+                        Err(String::from("The value is not a user ID"))
+                    }
+                })
+                .conflicts_with_all(&[
+                    "search-query",
+                    "cluster-id",
+                    "related",
+                    "cites",
+                    "html",
+                    "format",
+                ])
+                .display_order(16),
+        )
+        .arg(
+            Arg::with_name("find-author")
+                .long("find-author")
+                .help("Search authors with this name")
+                .value_name("name")
+                .conflicts_with_all(&[
+                    "search-query",
+                    "cluster-id",
+                    "related",
+                    "cites",
+                    "author-profile",
+                    "html",
+                    "format",
+                ])
+                .display_order(17),
         )
         .arg(
             Arg::with_name("search-html")
@@ -185,6 +472,18 @@ fn app() -> App<'static, 'static> {
                 .help("Output in JSON format")
                 .display_order(20),
         )
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .help(
+                    "Output in this bibliography format. \
+                     Citers found recursively are listed together.",
+                )
+                .takes_value(true)
+                .possible_values(&["bibtex", "ris", "csl-json"])
+                .conflicts_with("json")
+                .display_order(19),
+        )
         .arg(
             Arg::with_name("recursive")
                 .short("r")
@@ -204,18 +503,174 @@ fn app() -> App<'static, 'static> {
                 })
                 .display_order(21),
         )
+        .arg(
+            Arg::with_name("skip-citation-only")
+                .long("skip-citation-only")
+                .help(
+                    "Skip [CITATION] entries, which Google Scholar knows only from citations, \
+                     in any results including citers, after they are scraped",
+                )
+                .display_order(22),
+        )
+        .arg(
+            Arg::with_name("delay")
+                .long("delay")
+                .help(
+                    "Minimum interval between requests in seconds, \
+                     randomized by up to 50% more (default = 2)",
+                )
+                .value_name("seconds")
+                .validator(|v| match v.parse::<f64>() {
+                    Ok(v) if v >= 0.0 => Ok(()),
+                    _ => Err(String::from("The value is not a zero or positive number")),
+                })
+                .display_order(30),
+        )
+        .arg(
+            Arg::with_name("max-requests")
+                .long("max-requests")
+                .help("Stop after sending this number of requests")
+                .takes_value(true)
+                .validator(|v| match v.parse::<u32>() {
+                    Ok(v) if v > 0 => Ok(()),
+                    _ => Err(String::from("The value is not a positive integer")),
+                })
+                .display_order(31),
+        )
+        .arg(
+            Arg::with_name("retries")
+                .long("retries")
+                .help(
+                    "Retry a request failed by a network or server error up to this number of times, \
+                     with exponential backoff (default = 3)",
+                )
+                .takes_value(true)
+                .validator(|v| match v.parse::<u32>() {
+                    Ok(_) => Ok(()),
+                    _ => Err(String::from("The value is not a zero or positive integer")),
+                })
+                .display_order(32),
+        )
+        .arg(
+            Arg::with_name("wait-if-blocked")
+                .long("wait-if-blocked")
+                .help(
+                    "Wait this number of seconds and retry if Google Scholar blocked a request, \
+                     up to --retries times (default = stop)",
+                )
+                .value_name("seconds")
+                .validator(|v| match v.parse::<u64>() {
+                    Ok(_) => Ok(()),
+                    _ => Err(String::from("The value is not a zero or positive integer")),
+                })
+                .display_order(33),
+        )
+        .arg(
+            Arg::with_name("cache-dir")
+                .long("cache-dir")
+                .help(
+                    "Cache responses in this directory, reusing them in later runs \
+                     (default = no cache)",
+                )
+                .value_name("dir")
+                .conflicts_with_all(&["record", "replay"])
+                .display_order(34),
+        )
+        .arg(
+            Arg::with_name("cache-ttl")
+                .long("cache-ttl")
+                .help("Reuse cached responses for this number of seconds (default = 86400)")
+                .value_name("seconds")
+                .validator(|v| match v.parse::<u64>() {
+                    Ok(_) => Ok(()),
+                    _ => Err(String::from("The value is not a zero or positive integer")),
+                })
+                .requires("cache-dir")
+                .display_order(35),
+        )
+        .arg(
+            Arg::with_name("no-cache")
+                .long("no-cache")
+                .help("Neither use nor store cached responses, as without --cache-dir")
+                .conflicts_with_all(&["cache-dir", "cache-ttl", "offline", "clear-cache"])
+                .display_order(36),
+        )
+        .arg(
+            Arg::with_name("offline")
+                .long("offline")
+                .help("Use only cached responses, even if expired, without sending requests")
+                .requires("cache-dir")
+                .display_order(37),
+        )
+        .arg(
+            Arg::with_name("clear-cache")
+                .long("clear-cache")
+                .help("Remove expired responses from the cache, and exit")
+                .requires("cache-dir")
+                .display_order(38),
+        )
+        .arg(
+            Arg::with_name("record")
+                .long("record")
+                .help("Record requests and responses to this directory")
+                .value_name("dir")
+                .conflicts_with("replay")
+                .display_order(39),
+        )
+        .arg(
+            Arg::with_name("replay")
+                .long("replay")
+                .help(
+                    "Replay responses recorded by --record in this directory, \
+                     without sending requests",
+                )
+                .value_name("dir")
+                .display_order(40),
+        )
+        .arg(
+            Arg::with_name("base-url")
+                .long("base-url")
+                .help(
+                    "Send requests to Google Scholar of this URL, e.g. a regional domain \
+                     like https://scholar.google.co.jp/ (default = https://scholar.google.com/)",
+                )
+                .value_name("url")
+                .validator(|v| match BaseUrl::new(&v) {
+                    Ok(_) => Ok(()),
+                    _ => Err(String::from("The value is not an HTTP(S) URL")),
+                })
+                .display_order(41),
+        )
+        .arg(
+            Arg::with_name("language")
+                .long("language")
+                .help("Interface language of Google Scholar (default = en)")
+                .takes_value(true)
+                .possible_values(&["en", "ja", "de"])
+                .display_order(42),
+        )
         .arg(
             Arg::with_name("verbose")
                 .short("v")
                 .long("verbose")
                 .help("Verbose mode")
-                .display_order(22),
+                .display_order(23),
         )
 }
 
+fn validate_year(v: String) -> ::std::result::Result<(), String> {
+    match v.parse::<u32>() {
+        Ok(_) => Ok(()),
+        _ => Err(String::from("The value is not a year")),
+    }
+}
+
 fn query_exists(matches: &ArgMatches) -> bool {
     matches.is_present("search-query") || matches.is_present("html")
-        || matches.is_present("cluster-id")
+        || matches.is_present("cluster-id") || matches.is_present("related")
+        || matches.is_present("cites")
+        || matches.is_present("author-profile") || matches.is_present("find-author")
+        || matches.is_present("clear-cache")
 }
 
 #[cfg(test)]
@@ -264,6 +719,46 @@ mod tests {
             ArgumentConflict
         );
 
+        assert_eq!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--related", "0"])
+                .unwrap_err()
+                .kind,
+            ArgumentConflict
+        );
+
+        assert_eq!(
+            app()
+                .get_matches_from_safe(&["prog", "--related", "0", "--cluster-id", "0"])
+                .unwrap_err()
+                .kind,
+            ArgumentConflict
+        );
+
+        assert_eq!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--author-profile", "bar"])
+                .unwrap_err()
+                .kind,
+            ArgumentConflict
+        );
+
+        assert_eq!(
+            app()
+                .get_matches_from_safe(&["prog", "--author-profile", "bar", "--cluster-id", "0"])
+                .unwrap_err()
+                .kind,
+            ArgumentConflict
+        );
+
+        assert_eq!(
+            app()
+                .get_matches_from_safe(&["prog", "--find-author", "foo", "--authors", "foo"])
+                .unwrap_err()
+                .kind,
+            ArgumentConflict
+        );
+
         assert_eq!(
             app()
                 .get_matches_from_safe(&[
@@ -279,6 +774,329 @@ mod tests {
         );
     }
 
+    #[test]
+    fn app_count_test() {
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--count", "20"])
+                .is_ok()
+        );
+
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--count", "1001"])
+                .is_err()
+        );
+
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--count", "0"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn app_format_test() {
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--format", "csl-json"])
+                .is_ok()
+        );
+
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--format", "json"])
+                .is_err()
+        );
+
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--format", "ris", "--json"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn app_rate_limit_test() {
+        use std::time::Duration;
+
+        let matches = app().get_matches_from(&["prog", "--words", "foo", "--delay", "0.5"]);
+        let cfg = Config::new(&matches);
+        assert_eq!(cfg.rate_limit.min_interval, Duration::from_millis(500));
+        assert_eq!(cfg.rate_limit.jitter, Duration::from_millis(250));
+        assert_eq!(cfg.rate_limit.max_requests, None);
+
+        let matches = app().get_matches_from(&["prog", "--words", "foo", "--max-requests", "3"]);
+        let cfg = Config::new(&matches);
+        assert_eq!(cfg.rate_limit.max_requests, Some(3));
+
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--delay", "-1"])
+                .is_err()
+        );
+
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--max-requests", "0"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn app_retry_test() {
+        use std::time::Duration;
+
+        use scholar::retry::{BlockedAction, RetryPolicy};
+
+        let matches = app().get_matches_from(&["prog", "--words", "foo"]);
+        let cfg = Config::new(&matches);
+        assert_eq!(cfg.retry, RetryPolicy::default());
+
+        let matches = app().get_matches_from(&[
+            "prog",
+            "--words",
+            "foo",
+            "--retries",
+            "5",
+            "--wait-if-blocked",
+            "600",
+        ]);
+        let cfg = Config::new(&matches);
+        assert_eq!(cfg.retry.max_retries, 5);
+        assert_eq!(
+            cfg.retry.on_blocked,
+            BlockedAction::Wait(Duration::from_secs(600))
+        );
+
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--retries", "-1"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn app_cache_test() {
+        use std::path::PathBuf;
+        use std::time::Duration;
+
+        let matches = app().get_matches_from(&[
+            "prog",
+            "--words",
+            "foo",
+            "--cache-dir",
+            "/tmp/foo",
+            "--cache-ttl",
+            "60",
+            "--offline",
+        ]);
+        let cache = Config::new(&matches).cache.unwrap();
+        assert_eq!(cache.dir, PathBuf::from("/tmp/foo"));
+        assert_eq!(cache.ttl, Some(Duration::from_secs(60)));
+        assert!(cache.offline);
+
+        // Not cached by default.
+        let matches = app().get_matches_from(&["prog", "--words", "foo"]);
+        assert!(Config::new(&matches).cache.is_none());
+        let matches = app().get_matches_from(&["prog", "--words", "foo", "--no-cache"]);
+        assert!(Config::new(&matches).cache.is_none());
+
+        for args in &[
+            vec!["prog", "--words", "foo", "--offline"],
+            vec!["prog", "--words", "foo", "--cache-ttl", "60"],
+            vec!["prog", "--clear-cache"],
+            vec!["prog", "--words", "foo", "--cache-dir", "foo", "--record", "bar"],
+            vec!["prog", "--words", "foo", "--cache-dir", "foo", "--no-cache"],
+        ] {
+            assert!(app().get_matches_from_safe(args).is_err());
+        }
+    }
+
+    #[test]
+    fn app_archive_test() {
+        use std::path::PathBuf;
+        use std::time::Duration;
+
+        use config::ArchiveMode;
+
+        let matches = app().get_matches_from(&["prog", "--words", "foo", "--record", "foo"]);
+        let cfg = Config::new(&matches);
+        match cfg.archive {
+            Some(ArchiveMode::Record(ref dir)) => assert_eq!(*dir, PathBuf::from("foo")),
+            _ => panic!("not recording"),
+        }
+        assert!(cfg.cache.is_none());
+
+        let matches = app().get_matches_from(&["prog", "--words", "foo", "--replay", "foo"]);
+        let cfg = Config::new(&matches);
+        match cfg.archive {
+            Some(ArchiveMode::Replay(ref dir)) => assert_eq!(*dir, PathBuf::from("foo")),
+            _ => panic!("not replaying"),
+        }
+        assert!(cfg.cache.is_none());
+        assert_eq!(cfg.rate_limit.min_interval, Duration::from_secs(0));
+
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--record", "a", "--replay", "b"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn app_base_url_test() {
+        let matches = app().get_matches_from(&["prog", "--words", "foo"]);
+        assert_eq!(Config::new(&matches).base_url, BaseUrl::default());
+
+        let matches = app().get_matches_from(&[
+            "prog",
+            "--words",
+            "foo",
+            "--base-url",
+            "https://scholar.google.co.jp",
+        ]);
+        assert_eq!(
+            Config::new(&matches).base_url,
+            BaseUrl::regional("co.jp").unwrap()
+        );
+
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--base-url", "foo"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn app_language_test() {
+        use scholar::language::Language;
+
+        let matches = app().get_matches_from(&["prog", "--words", "foo"]);
+        assert_eq!(Config::new(&matches).language, Language::English);
+
+        let matches = app().get_matches_from(&["prog", "--words", "foo", "--language", "ja"]);
+        assert_eq!(Config::new(&matches).language, Language::Japanese);
+
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--language", "xx"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn app_collection_test() {
+        use scholar::request::{Collection, Jurisdiction};
+
+        let collection = |args: &[&str]| Config::new(&app().get_matches_from(args)).collection;
+
+        assert_eq!(
+            collection(&["prog", "--words", "foo"]),
+            Collection::Articles {
+                include_patents: true,
+            }
+        );
+        assert_eq!(
+            collection(&["prog", "--words", "foo", "--no-patents"]),
+            Collection::Articles {
+                include_patents: false,
+            }
+        );
+        assert_eq!(
+            collection(&["prog", "--case-law", "--words", "foo"]),
+            Collection::CaseLaw(Jurisdiction::AllCourts)
+        );
+        assert_eq!(
+            collection(&["prog", "--words", "foo", "--case-law", "33,192"]),
+            Collection::CaseLaw(Jurisdiction::Courts(vec![33, 192]))
+        );
+
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--case-law", "foo"])
+                .is_err()
+        );
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--words", "foo", "--case-law", "--no-patents"])
+                .is_err()
+        );
+
+        for option in &["--no-patents", "--no-citations", "--case-law"] {
+            for query in &["--cites", "--related"] {
+                assert_eq!(
+                    app()
+                        .get_matches_from_safe(&["prog", query, "0", option])
+                        .unwrap_err()
+                        .kind,
+                    clap::ErrorKind::ArgumentConflict
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn app_no_citations_test() {
+        let matches = app().get_matches_from(&["prog", "--words", "foo", "--no-citations"]);
+        assert!(matches.is_present("no-citations"));
+        assert!(!Config::new(&matches).skip_citation_only);
+
+        let matches = app().get_matches_from(&["prog", "--words", "foo", "--skip-citation-only"]);
+        assert!(!matches.is_present("no-citations"));
+        assert!(Config::new(&matches).skip_citation_only);
+    }
+
+    #[test]
+    fn app_cites_test() {
+        let matches = app().get_matches_from(&[
+            "prog",
+            "--cites",
+            "5545735591029960915",
+            "--words",
+            "quantum",
+            "--authors",
+            "berry",
+        ]);
+        assert_eq!(
+            value_t!(matches, "cites", u64).unwrap(),
+            5545735591029960915
+        );
+        assert_eq!(matches.value_of("words"), Some("quantum"));
+
+        let matches = app().get_matches_from(&[
+            "prog",
+            "--cites",
+            "0",
+            "--phrase",
+            "phase factor",
+            "--year-from",
+            "2015",
+            "--year-to",
+            "2018",
+        ]);
+        assert_eq!(value_t!(matches, "year-from", u32).unwrap(), 2015);
+        assert_eq!(value_t!(matches, "year-to", u32).unwrap(), 2018);
+
+        for arg in &[
+            "--cluster-id",
+            "--related",
+            "--without",
+            "--published-in",
+        ] {
+            assert!(
+                app()
+                    .get_matches_from_safe(&["prog", "--cites", "0", arg, "2000"])
+                    .is_err()
+            );
+        }
+        assert!(
+            app()
+                .get_matches_from_safe(&["prog", "--cites", "foo"])
+                .is_err()
+        );
+    }
+
     #[test]
     fn query_exists_test() {
         assert!(query_exists(&app().get_matches_from(&["prog", "--words", "foo"])));
@@ -295,12 +1113,42 @@ mod tests {
             "foo"
         ])));
 
+        assert!(query_exists(&app().get_matches_from(&[
+            "prog",
+            "--published-in",
+            "foo"
+        ])));
+
         assert!(query_exists(&app().get_matches_from(&[
             "prog",
             "--cluster-id",
             "0"
         ])));
 
+        assert!(query_exists(&app().get_matches_from(&[
+            "prog",
+            "--related",
+            "0"
+        ])));
+
+        assert!(query_exists(&app().get_matches_from(&[
+            "prog",
+            "--cites",
+            "0"
+        ])));
+
+        assert!(query_exists(&app().get_matches_from(&[
+            "prog",
+            "--author-profile",
+            "lpTL7HwAAAAJ"
+        ])));
+
+        assert!(query_exists(&app().get_matches_from(&[
+            "prog",
+            "--find-author",
+            "foo"
+        ])));
+
         assert!(query_exists(&app().get_matches_from(&[
             "prog",
             "--search-html",
@@ -313,8 +1161,17 @@ mod tests {
             "foo.html"
         ])));
 
+        assert!(query_exists(&app().get_matches_from(&[
+            "prog",
+            "--cache-dir",
+            "foo",
+            "--clear-cache"
+        ])));
+
         assert!(!query_exists(&app().get_matches_from(&["prog"])));
 
         assert!(!query_exists(&app().get_matches_from(&["prog", "--count", "1"])));
+
+        assert!(!query_exists(&app().get_matches_from(&["prog", "--without", "foo"])));
     }
 }
@@ -1,28 +1,31 @@
+use std::fmt;
+
 use serde_json;
 
-use scholar::paper::Paper;
-use scholar::request::{send_request, CitationQuery};
-use scholar::scrape::{CitationDocument, ClusterDocument, PapersDocument, SearchDocument};
+use scholar::client::ScholarClient;
+use scholar::errors::ErrorKind as ScholarErrorKind;
+use scholar::paper::{export, Paper, PaperKind};
+use scholar::request::{AuthorProfileQuery, AuthorSearchQuery, CitationQuery, ClusterQuery,
+                       PagedQuery};
+use scholar::scrape::{CitationDocument, PapersDocument, SearchDocument};
 
-use config::{Config, OutputFormat};
+use config::{Config, ExportFormat, OutputFormat};
 use errors::*;
 
 macro_rules! exit_blocked {
     ($doc: ident) => {
         if $doc.is_blocked() {
-            return Err(ErrorKind::Blocked.into());
+            return Err(ErrorKind::Scholar(ScholarErrorKind::Blocked).into());
         }
     }
 }
 
-pub fn scrape_cluster_doc(doc: &ClusterDocument, cfg: &Config) -> Result<()> {
-    exit_blocked!(doc);
-
+pub fn search_cluster(client: &ScholarClient, query: &ClusterQuery, cfg: &Config) -> Result<()> {
     let paper = {
-        let mut p = doc.scrape_target_paper()?;
+        let mut p = client.cluster(query)?;
 
         if cfg.recursive_depth > 0 {
-            p = recursive_search(&p, cfg)?;
+            p = recursive_search(client, &p, cfg)?;
         }
 
         p
@@ -36,58 +39,97 @@ pub fn scrape_cluster_doc(doc: &ClusterDocument, cfg: &Config) -> Result<()> {
         OutputFormat::Json => {
             println!("{}", serde_json::to_string_pretty(&paper)?);
         }
+        OutputFormat::Exported(format) => output_exported(&[paper], format)?,
     }
 
     Ok(())
 }
 
-pub fn scrape_citaiton_doc(doc: &CitationDocument, cfg: &Config) -> Result<()> {
+pub fn scrape_citaiton_doc(
+    client: &ScholarClient,
+    doc: &CitationDocument,
+    cfg: &Config,
+) -> Result<()> {
     exit_blocked!(doc);
 
-    let paper = {
-        let mut p = doc.scrape_target_paper_with_citers()?;
+    let paper = doc.scrape_target_paper_with_citers()?;
+    output_citations(client, paper, cfg)
+}
 
-        if cfg.recursive_depth > 0 {
-            let new_citers = p.citers
-                .unwrap()
-                .iter()
-                .flat_map(|c| recursive_search(c, cfg))
-                .collect();
-            p.citers = Some(new_citers);
-        }
+pub fn search_citations(client: &ScholarClient, query: &CitationQuery, cfg: &Config) -> Result<()> {
+    let total_count = cfg.max_result_count.unwrap_or_else(|| query.get_count());
+    let paper = client.citations(query, total_count)?;
+    output_citations(client, paper, cfg)
+}
 
-        p
-    };
+pub fn scrape_search_doc(client: &ScholarClient, doc: &SearchDocument, cfg: &Config) -> Result<()> {
+    exit_blocked!(doc);
+
+    let papers = doc.scrape_papers()?;
+    output_search_results(client, papers, cfg)
+}
+
+pub fn search_papers<Q>(client: &ScholarClient, query: &Q, cfg: &Config) -> Result<()>
+where
+    Q: PagedQuery + Clone + fmt::Display,
+{
+    let total_count = cfg.max_result_count.unwrap_or_else(|| query.get_count());
+    let papers = client.search(query, total_count)?;
+    output_search_results(client, papers, cfg)
+}
+
+pub fn search_authors(client: &ScholarClient, query: &AuthorSearchQuery, cfg: &Config) -> Result<()> {
+    let authors = client.search_authors(query)?;
+
+    match cfg.output_format {
+        OutputFormat::HumanReadable => for author in authors {
+            println!("Result:\n");
+            println!("{}\n", author);
+        },
+        _ => for author in authors {
+            println!("{}", serde_json::to_string_pretty(&author)?);
+        },
+    }
+
+    Ok(())
+}
+
+pub fn author_profile(
+    client: &ScholarClient,
+    query: &AuthorProfileQuery,
+    cfg: &Config,
+) -> Result<()> {
+    let publication_count = cfg.max_result_count
+        .unwrap_or_else(|| query.get_page_size());
+    let profile = client.author_profile(query, publication_count)?;
 
     match cfg.output_format {
         OutputFormat::HumanReadable => {
-            println!("The target paper:\n");
-            println!("{}\n", paper);
+            println!("Author:\n");
+            println!("{}\n", profile);
 
-            println!("... is cited by:\n");
-            for citer in paper.citers.unwrap() {
-                println!("{}\n", citer);
+            println!("Publications:\n");
+            for publication in &profile.publications {
+                println!("{}\n", publication);
             }
         }
-        OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&paper)?);
+        _ => {
+            println!("{}", serde_json::to_string_pretty(&profile)?);
         }
     }
 
     Ok(())
 }
 
-pub fn scrape_search_doc(doc: &SearchDocument, cfg: &Config) -> Result<()> {
-    exit_blocked!(doc);
-
+fn output_search_results(client: &ScholarClient, papers: Vec<Paper>, cfg: &Config) -> Result<()> {
     let papers = {
-        let mut papers = doc.scrape_papers()?;
+        let mut papers = filter_papers(papers, cfg);
 
         if cfg.recursive_depth > 0 {
             papers = papers
                 .iter()
-                .flat_map(|p| recursive_search(p, cfg))
-                .collect();
+                .map(|p| recursive_search(client, p, cfg))
+                .collect::<Result<Vec<_>>>()?;
         }
 
         papers
@@ -101,12 +143,55 @@ pub fn scrape_search_doc(doc: &SearchDocument, cfg: &Config) -> Result<()> {
         OutputFormat::Json => for paper in papers {
             println!("{}", serde_json::to_string_pretty(&paper)?);
         },
+        OutputFormat::Exported(format) => output_exported(&papers, format)?,
+    }
+
+    Ok(())
+}
+
+fn output_citations(client: &ScholarClient, mut paper: Paper, cfg: &Config) -> Result<()> {
+    paper.citers = paper.citers.map(|c| filter_papers(c, cfg));
+
+    if cfg.recursive_depth > 0 {
+        let new_citers = paper
+            .citers
+            .unwrap()
+            .iter()
+            .map(|c| recursive_search(client, c, cfg))
+            .collect::<Result<Vec<_>>>()?;
+        paper.citers = Some(new_citers);
+    }
+
+    match cfg.output_format {
+        OutputFormat::HumanReadable => {
+            println!("The target paper:\n");
+            println!("{}\n", paper);
+
+            println!("... is cited by:\n");
+            for citer in paper.citers.unwrap() {
+                println!("{}\n", citer);
+            }
+        }
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&paper)?);
+        }
+        OutputFormat::Exported(format) => output_exported(&[paper], format)?,
+    }
+
+    Ok(())
+}
+
+fn output_exported(papers: &[Paper], format: ExportFormat) -> Result<()> {
+    match format {
+        ExportFormat::Bibtex => print!("{}", export::to_bibtex(papers)),
+        ExportFormat::Ris => print!("{}", export::to_ris(papers)),
+        ExportFormat::CslJson => println!("{}", export::to_csl_json(papers)?),
     }
 
     Ok(())
 }
 
-fn recursive_search(paper: &Paper, cfg: &Config) -> Result<Paper> {
+fn recursive_search(client: &ScholarClient, paper: &Paper, cfg: &Config) -> Result<Paper> {
     if cfg.recursive_depth == 0 {
         return Ok(paper.clone());
     }
@@ -119,26 +204,224 @@ fn recursive_search(paper: &Paper, cfg: &Config) -> Result<Paper> {
         c
     };
 
-    let query = {
-        let mut q = CitationQuery::new(&paper.citation_url);
-        if let Some(count) = cfg.max_result_count {
-            q.set_count(count);
-        }
-        q
-    };
+    let mut query = CitationQuery::new(&paper.citation_url);
+    query.set_sort_order(cfg.sort_order);
+    let total_count = cfg.max_result_count.unwrap_or_else(|| query.get_count());
+    let mut new_paper = client.citations(&query, total_count)?;
 
-    let body = send_request(&query, cfg.verbose)?;
-    let doc = CitationDocument::from(&*body);
-    exit_blocked!(doc);
-
-    let mut new_paper = doc.scrape_target_paper_with_citers()?;
-    let new_citers = new_paper
-        .citers
-        .unwrap()
+    let new_citers = filter_papers(new_paper.citers.unwrap(), cfg)
         .iter()
-        .flat_map(|c| recursive_search(c, &new_cfg))
-        .collect();
+        .map(|c| recursive_search(client, c, &new_cfg))
+        .collect::<Result<Vec<_>>>()?;
     new_paper.citers = Some(new_citers);
 
     Ok(new_paper)
 }
+
+fn filter_papers(papers: Vec<Paper>, cfg: &Config) -> Vec<Paper> {
+    if !cfg.skip_citation_only {
+        return papers;
+    }
+
+    papers
+        .into_iter()
+        .filter(|p| p.kind != Some(PaperKind::Citation))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::Arc;
+
+    use scholar::request::{Query, SortOrder};
+    use scholar::retry::RetryPolicy;
+    use scholar::transport::MemoryTransport;
+
+    use super::*;
+    use test_util::test_client;
+
+    fn target_paper() -> Paper {
+        let file = fs::File::open("src/test_html/quantum_theory.html").unwrap();
+        let doc = SearchDocument::from_read(file).unwrap();
+        doc.scrape_papers()
+            .unwrap()
+            .into_iter()
+            .find(|p| p.cluster_id == 5545735591029960915)
+            .unwrap()
+    }
+
+    fn citation_query(paper: &Paper, count: u32) -> CitationQuery {
+        let mut query = CitationQuery::new(&paper.citation_url);
+        query.set_count(count);
+        query
+    }
+
+    #[test]
+    fn recursive_search_test() {
+        let paper = target_paper();
+        let url = citation_query(&paper, 10).to_url().unwrap();
+
+        let transport = {
+            let mut t = MemoryTransport::new();
+            t.insert_file(&url, "src/test_html/quantum_theory_citations.html")
+                .unwrap();
+            Arc::new(t)
+        };
+        let client = test_client(transport.clone(), RetryPolicy::default());
+
+        let matches = ::app().get_matches_from(&[
+            "prog",
+            "--cluster-id",
+            "5545735591029960915",
+            "--count",
+            "10",
+            "--recursive",
+            "1",
+        ]);
+        let cfg = Config::new(&matches);
+
+        let paper = recursive_search(&client, &paper, &cfg).unwrap();
+        assert_eq!(
+            paper.title,
+            "Significance of electromagnetic potentials in the quantum theory"
+        );
+
+        let citers = paper.citers.unwrap();
+        assert_eq!(citers.len(), 10);
+        assert_eq!(
+            citers[0].title,
+            "Quantal phase factors accompanying adiabatic changes"
+        );
+        assert!(citers.iter().all(|c| c.citers.is_none()));
+
+        assert_eq!(transport.requested_urls(), vec![url.to_string()]);
+    }
+
+    #[test]
+    fn recursive_search_sorted_by_date_test() {
+        let paper = target_paper();
+        let url = {
+            let mut query = citation_query(&paper, 10);
+            query.set_sort_order(SortOrder::Date);
+            query.to_url().unwrap()
+        };
+
+        let transport = {
+            let mut t = MemoryTransport::new();
+            t.insert_file(&url, "src/test_html/quantum_theory_citations_date.html")
+                .unwrap();
+            Arc::new(t)
+        };
+        let client = test_client(transport.clone(), RetryPolicy::default());
+
+        let matches = ::app().get_matches_from(&[
+            "prog",
+            "--cluster-id",
+            "5545735591029960915",
+            "--count",
+            "10",
+            "--recursive",
+            "1",
+            "--sort-by-date",
+        ]);
+        let cfg = Config::new(&matches);
+
+        let citers = recursive_search(&client, &paper, &cfg)
+            .unwrap()
+            .citers
+            .unwrap();
+        assert_eq!(citers[0].days_ago, Some(1));
+        assert_eq!(citers[9].days_ago, Some(14));
+
+        assert_eq!(transport.requested_urls(), vec![url.to_string()]);
+    }
+
+    #[test]
+    fn recursive_search_nested_blocked_test() {
+        let paper = target_paper();
+        let url = citation_query(&paper, 10).to_url().unwrap();
+
+        let citers = {
+            let file = fs::File::open("src/test_html/quantum_theory_citations.html").unwrap();
+            let doc = CitationDocument::from_read(file).unwrap();
+            doc.scrape_papers().unwrap()
+        };
+        let nested_url = citation_query(&citers[0], 10).to_url().unwrap();
+
+        let transport = {
+            let mut t = MemoryTransport::new();
+            t.insert_file(&url, "src/test_html/quantum_theory_citations.html")
+                .unwrap();
+            t.insert_file(&nested_url, "src/test_html/blocked.html")
+                .unwrap();
+            Arc::new(t)
+        };
+        let client = test_client(transport.clone(), RetryPolicy::default());
+
+        let matches = ::app().get_matches_from(&[
+            "prog",
+            "--cluster-id",
+            "5545735591029960915",
+            "--count",
+            "10",
+            "--recursive",
+            "2",
+        ]);
+        let cfg = Config::new(&matches);
+
+        match recursive_search(&client, &paper, &cfg) {
+            Err(Error(ErrorKind::Scholar(ScholarErrorKind::Blocked), _)) => {}
+            _ => panic!("not blocked"),
+        }
+        // The crawl stops at the blocked page.
+        assert_eq!(
+            transport.requested_urls(),
+            vec![url.to_string(), nested_url.to_string()]
+        );
+    }
+
+    #[test]
+    fn scrape_blocked_doc_test() {
+        let path = "src/test_html/blocked.html";
+        let doc = SearchDocument::from_read(fs::File::open(path).unwrap()).unwrap();
+        let client = test_client(Arc::new(MemoryTransport::new()), RetryPolicy::default());
+        let cfg = Config::new(&::app().get_matches_from(&["prog", "--search-html", path]));
+
+        // The same error as pages blocked over network.
+        match scrape_search_doc(&client, &doc, &cfg) {
+            Err(Error(ErrorKind::Scholar(ScholarErrorKind::Blocked), _)) => {}
+            _ => panic!("not blocked"),
+        }
+    }
+
+    #[test]
+    fn recursive_search_blocked_test() {
+        let paper = target_paper();
+        let url = citation_query(&paper, 10).to_url().unwrap();
+
+        let transport = {
+            let mut t = MemoryTransport::new();
+            t.insert_file(&url, "src/test_html/blocked.html").unwrap();
+            Arc::new(t)
+        };
+        let client = test_client(transport.clone(), RetryPolicy::default());
+
+        let matches = ::app().get_matches_from(&[
+            "prog",
+            "--cluster-id",
+            "5545735591029960915",
+            "--count",
+            "10",
+            "--recursive",
+            "1",
+        ]);
+        let cfg = Config::new(&matches);
+
+        match recursive_search(&client, &paper, &cfg) {
+            Err(Error(ErrorKind::Scholar(ScholarErrorKind::Blocked), _)) => {}
+            _ => panic!("not blocked"),
+        }
+        assert_eq!(transport.requested_urls().len(), 1);
+    }
+}
@@ -0,0 +1,272 @@
+//! On-disk cache of response bodies, keyed by normalized query URLs.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime};
+
+use reqwest::Url;
+
+use errors::*;
+
+/// Configuration of the response cache of `ScholarClient`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// Directory to store cached responses in, created if missing.
+    pub dir: PathBuf,
+    /// Time to live of cached responses. `None` for no expiration.
+    pub ttl: Option<Duration>,
+    /// Serve responses only from the cache, failing with `ErrorKind::CacheMiss` if not cached.
+    /// Expired responses are served too.
+    pub offline: bool,
+}
+
+impl CacheConfig {
+    /// Cache in `dir`, expiring in a day.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl: Some(Duration::from_secs(24 * 60 * 60)),
+            offline: false,
+        }
+    }
+}
+
+/// Response bodies cached in a directory, one file per URL.
+pub struct DiskCache {
+    dir: PathBuf,
+    ttl: Option<Duration>,
+}
+
+impl DiskCache {
+    /// Create new `DiskCache` in `dir` with `ttl`. `None` for no expiration.
+    pub fn new<P: Into<PathBuf>>(dir: P, ttl: Option<Duration>) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl,
+        }
+    }
+
+    /// Get the cached body of `url`.
+    ///
+    /// # Return value
+    ///
+    /// `Ok(None)` if `url` is not cached or the cached response has expired.
+    pub fn get(&self, url: &Url) -> Result<Option<String>> {
+        let key = normalize_url(url);
+        let path = self.entry_path(&key);
+
+        let mut file = match File::open(&path) {
+            Ok(file) => file,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        if self.is_stale(&path)? {
+            return Ok(None);
+        }
+
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+
+        // The first line is the URL, guarding against hash collisions.
+        let newline_pos = match content.find('\n') {
+            Some(pos) => pos,
+            None => return Ok(None),
+        };
+        if content[..newline_pos] != key {
+            return Ok(None);
+        }
+
+        Ok(Some(content[newline_pos + 1..].to_owned()))
+    }
+
+    /// Cache `body` as the response of `url`.
+    pub fn put(&self, url: &Url, body: &str) -> Result<()> {
+        lazy_static! {
+            static ref TEMP_COUNT: AtomicUsize = AtomicUsize::new(0);
+        }
+
+        let key = normalize_url(url);
+        fs::create_dir_all(&self.dir)?;
+
+        // Write to a temporary file and rename it into place,
+        // so that an interrupted write never leaves a truncated entry.
+        let temp_path = self.dir.join(format!(
+            "{}-{}.{}",
+            process::id(),
+            TEMP_COUNT.fetch_add(1, Ordering::SeqCst),
+            TEMP_EXTENSION
+        ));
+        let written = File::create(&temp_path).and_then(|mut file| {
+            writeln!(file, "{}", key)?;
+            file.write_all(body.as_bytes())?;
+            file.sync_all()
+        });
+        if let Err(e) = written.and_then(|_| fs::rename(&temp_path, self.entry_path(&key))) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+
+    /// Remove expired responses, and temporary files left by interrupted writes.
+    ///
+    /// # Return value
+    ///
+    /// `Ok` of the number of removed responses.
+    pub fn clear_stale(&self) -> Result<u32> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut count = 0;
+        for entry in entries {
+            let path = entry?.path();
+            let extension = path.extension();
+            if extension == Some(ENTRY_EXTENSION.as_ref()) && self.is_stale(&path)? {
+                fs::remove_file(&path)?;
+                count += 1;
+            } else if extension == Some(TEMP_EXTENSION.as_ref()) && self.is_stale(&path)? {
+                fs::remove_file(&path)?;
+            }
+        }
+
+        Ok(count)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:016x}.{}", fnv1a_hash(key), ENTRY_EXTENSION))
+    }
+
+    fn is_stale(&self, path: &Path) -> Result<bool> {
+        let ttl = match self.ttl {
+            Some(ttl) => ttl,
+            None => return Ok(false),
+        };
+
+        let modified = fs::metadata(path)?.modified()?;
+        // A modification time in the future is regarded as fresh.
+        let age = SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_else(|_| Duration::from_secs(0));
+        Ok(age >= ttl)
+    }
+}
+
+const ENTRY_EXTENSION: &str = "cache";
+const TEMP_EXTENSION: &str = "tmp";
+
+// URLs differing only in the order of query parameters or fragments share one entry.
+fn normalize_url(url: &Url) -> String {
+    let mut url = url.clone();
+    url.set_fragment(None);
+
+    let mut pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    if pairs.is_empty() {
+        url.set_query(None);
+    } else {
+        pairs.sort();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+
+    url.into_string()
+}
+
+// 64-bit FNV-1a, stable over Rust versions unlike `DefaultHasher`.
+fn fnv1a_hash(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_util::TestDir;
+
+    #[test]
+    fn normalize_url_test() {
+        let url = |s| Url::parse(s).unwrap();
+
+        assert_eq!(
+            normalize_url(&url("https://scholar.google.com/scholar?q=foo&hl=en#top")),
+            normalize_url(&url("https://scholar.google.com/scholar?hl=en&q=foo"))
+        );
+        assert_ne!(
+            normalize_url(&url("https://scholar.google.com/scholar?q=foo&hl=en")),
+            normalize_url(&url("https://scholar.google.com/scholar?q=foo&hl=ja"))
+        );
+        assert_eq!(
+            normalize_url(&url("https://scholar.google.com/scholar?")),
+            "https://scholar.google.com/scholar"
+        );
+    }
+
+    #[test]
+    fn fnv1a_hash_test() {
+        assert_eq!(fnv1a_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_hash("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn disk_cache_test() {
+        let dir = TestDir::new("cache-get-put");
+        let cache = DiskCache::new(&*dir, None);
+        let url = Url::parse("https://scholar.google.com/scholar?q=foo&hl=en").unwrap();
+        let other_url = Url::parse("https://scholar.google.com/scholar?q=bar&hl=en").unwrap();
+
+        assert_eq!(cache.get(&url).unwrap(), None);
+
+        cache.put(&url, "<html>\nfoo\n</html>").unwrap();
+        assert_eq!(
+            cache.get(&url).unwrap(),
+            Some(String::from("<html>\nfoo\n</html>"))
+        );
+        assert_eq!(
+            cache
+                .get(&Url::parse("https://scholar.google.com/scholar?hl=en&q=foo").unwrap())
+                .unwrap(),
+            Some(String::from("<html>\nfoo\n</html>"))
+        );
+        assert_eq!(cache.get(&other_url).unwrap(), None);
+        assert_eq!(cache.clear_stale().unwrap(), 0);
+
+        // No temporary file is left.
+        cache.put(&url, "bar").unwrap();
+        assert_eq!(cache.get(&url).unwrap(), Some(String::from("bar")));
+        let files = fs::read_dir(&*dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect::<Vec<_>>();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].extension(), Some(ENTRY_EXTENSION.as_ref()));
+    }
+
+    #[test]
+    fn disk_cache_ttl_test() {
+        let dir = TestDir::new("cache-ttl");
+        let url = Url::parse("https://scholar.google.com/scholar?q=foo&hl=en").unwrap();
+
+        DiskCache::new(&*dir, None).put(&url, "foo").unwrap();
+
+        // Left by an interrupted write.
+        let temp_path = dir.join(format!("0-0.{}", TEMP_EXTENSION));
+        File::create(&temp_path).unwrap();
+
+        let cache = DiskCache::new(&*dir, Some(Duration::from_secs(0)));
+        assert_eq!(cache.get(&url).unwrap(), None);
+        assert_eq!(cache.clear_stale().unwrap(), 1);
+        assert!(!temp_path.exists());
+        assert_eq!(DiskCache::new(&*dir, None).get(&url).unwrap(), None);
+
+        let missing_dir = TestDir::new("cache-missing");
+        assert_eq!(DiskCache::new(&*missing_dir, None).clear_stale().unwrap(), 0);
+    }
+}
@@ -0,0 +1,677 @@
+//! `ScholarClient`, a long-lived HTTP client sending queries to Google Scholar.
+
+use std::cmp;
+use std::fmt;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use reqwest::header;
+
+use super::MAX_RESULT_COUNT;
+use author::{AuthorProfile, AuthorSummary};
+use cache::{CacheConfig, DiskCache};
+use errors::*;
+use language::Language;
+use paginate;
+use paper::{BibtexEntry, Paper, Version};
+use rate_limit::{RateLimit, RateLimiter};
+use request::{AuthorProfileQuery, AuthorSearchQuery, BaseUrl, CitationQuery, CiteQuery,
+              ClusterQuery, ExportQuery, PagedQuery, Query, VersionsQuery};
+use retry::{RetryEvent, RetryPolicy, RetryReason};
+use scrape::{self, AuthorSearchDocument, CitationDocument, CiteDocument, ClusterDocument,
+             PapersDocument, SearchDocument, VersionsDocument};
+use transport::{ReqwestTransport, Response, Transport};
+
+const DEFAULT_USER_AGENT: &str =
+    "Mozilla/5.0 (X11; Linux x86_64; rv:57.0) Gecko/20100101 Firefox/57.0";
+
+/// Configuration of `ScholarClient`.
+#[derive(Clone, Debug)]
+pub struct ClientConfig {
+    /// Value of User-Agent header.
+    pub user_agent: String,
+    /// Timeout of each request. `None` for no timeout.
+    pub timeout: Option<Duration>,
+    /// Headers sent with every request, in addition to User-Agent and Cookie.
+    pub headers: header::Headers,
+    /// Pacing of requests.
+    pub rate_limit: RateLimit,
+    /// Retries of requests failed transiently or blocked.
+    pub retry: RetryPolicy,
+    /// Response cache on disk. `None` for no cache.
+    pub cache: Option<CacheConfig>,
+    /// Base URL of Google Scholar, which every query is sent to.
+    pub base_url: BaseUrl,
+    /// Interface language of Google Scholar pages.
+    pub language: Language,
+    /// Print every query and URL before sending it.
+    pub verbose: bool,
+}
+
+impl Default for ClientConfig {
+    /// User-Agent of Firefox, 30 seconds of timeout, no other headers,
+    /// the default `RateLimit` and `RetryPolicy`, no cache, and https://scholar.google.com/
+    /// in English.
+    fn default() -> Self {
+        Self {
+            user_agent: String::from(DEFAULT_USER_AGENT),
+            timeout: Some(Duration::from_secs(30)),
+            headers: header::Headers::new(),
+            rate_limit: RateLimit::default(),
+            retry: RetryPolicy::default(),
+            cache: None,
+            base_url: BaseUrl::default(),
+            language: Language::default(),
+            verbose: false,
+        }
+    }
+}
+
+type RetryObserver = dyn Fn(&RetryEvent) + Send + Sync;
+
+/// HTTP client reusing connections and cookies over requests to Google Scholar.
+pub struct ScholarClient {
+    transport: Box<dyn Transport>,
+    rate_limiter: Arc<RateLimiter>,
+    retry: RetryPolicy,
+    retry_observer: Option<Box<RetryObserver>>,
+    cache: Option<DiskCache>,
+    offline: bool,
+    base_url: BaseUrl,
+    language: Language,
+    verbose: bool,
+}
+
+impl ScholarClient {
+    /// Create new `ScholarClient` with `config`, sending requests over network.
+    pub fn new(config: ClientConfig) -> Result<Self> {
+        let rate_limiter = Arc::new(RateLimiter::new(config.rate_limit.clone()));
+        Self::with_rate_limiter(config, rate_limiter)
+    }
+
+    /// Create new `ScholarClient` with `config`, sharing `rate_limiter` with other clients.
+    /// `config.rate_limit` is ignored.
+    pub fn with_rate_limiter(config: ClientConfig, rate_limiter: Arc<RateLimiter>) -> Result<Self> {
+        let transport = ReqwestTransport::new(&config)?;
+        Ok(Self::build(config, Box::new(transport), rate_limiter))
+    }
+
+    /// Create new `ScholarClient` with `config`, fetching pages through `transport`.
+    /// User-Agent, timeout and headers of `config` are left to `transport`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::client::{ClientConfig, ScholarClient};
+    /// use scholar::transport::MemoryTransport;
+    ///
+    /// let client = ScholarClient::with_transport(ClientConfig::default(), MemoryTransport::new());
+    /// ```
+    pub fn with_transport<T: Transport + 'static>(config: ClientConfig, transport: T) -> Self {
+        let rate_limiter = Arc::new(RateLimiter::new(config.rate_limit.clone()));
+        Self::build(config, Box::new(transport), rate_limiter)
+    }
+
+    fn build(
+        config: ClientConfig,
+        transport: Box<dyn Transport>,
+        rate_limiter: Arc<RateLimiter>,
+    ) -> Self {
+        let (cache, offline) = match config.cache {
+            Some(c) => {
+                // Expired responses are better than nothing in offline mode.
+                let ttl = if c.offline { None } else { c.ttl };
+                (Some(DiskCache::new(c.dir, ttl)), c.offline)
+            }
+            None => (None, false),
+        };
+
+        Self {
+            transport,
+            rate_limiter,
+            retry: config.retry,
+            retry_observer: None,
+            cache,
+            offline,
+            base_url: config.base_url,
+            language: config.language,
+            verbose: config.verbose,
+        }
+    }
+
+    /// Set `observer` to be called before each retry of a request.
+    pub fn set_retry_observer<F>(&mut self, observer: F)
+    where
+        F: Fn(&RetryEvent) + Send + Sync + 'static,
+    {
+        self.retry_observer = Some(Box::new(observer));
+    }
+
+    /// Send a request of `query` through the transport and get the response body.
+    /// The URL of `query` is under the base URL, requesting the page in the language.
+    ///
+    /// If the cache is enabled, a cached response is returned without sending a request,
+    /// and a successful response is cached. In offline mode, a response not cached
+    /// results in `ErrorKind::CacheMiss`.
+    ///
+    /// Each attempt waits for the rate limiter, and fails with
+    /// `ErrorKind::RequestBudgetExceeded` if the request budget has run out.
+    /// Transient failures (network errors, timeouts and server errors) are retried following
+    /// the `RetryPolicy`. A blocked request fails with `ErrorKind::Blocked`,
+    /// unless the policy tells to wait and retry.
+    pub fn send<Q: Query + fmt::Display>(&self, query: &Q) -> Result<String> {
+        let url = query.to_localized_url(&self.base_url, self.language)?;
+
+        if self.verbose {
+            println!("Sending {}", query);
+            println!("(URL: {})", url);
+        }
+
+        if let Some(ref cache) = self.cache {
+            if let Some(body) = cache.get(&url)? {
+                if self.verbose {
+                    println!("(cached)");
+                }
+                return Ok(body);
+            }
+
+            if self.offline {
+                return Err(ErrorKind::CacheMiss(url.into_string()).into());
+            }
+        }
+
+        let mut retry = 0;
+        loop {
+            self.rate_limiter.acquire()?;
+
+            let (reason, error) = match self.transport.get(&url) {
+                Ok(response) => match check_response(response, self.language) {
+                    Outcome::Body(body) => {
+                        if let Some(ref cache) = self.cache {
+                            cache.put(&url, &body)?;
+                        }
+                        return Ok(body);
+                    }
+                    Outcome::Fail(e) => return Err(e),
+                    Outcome::Retry(reason, e) => (reason, e),
+                },
+                Err(e) => {
+                    if !e.is_transient() {
+                        return Err(e);
+                    }
+                    (RetryReason::Transport(e.to_string()), e)
+                }
+            };
+
+            let wait = match self.retry.backoff(retry, &reason) {
+                Some(wait) => wait,
+                None => return Err(error),
+            };
+
+            retry += 1;
+            if let Some(ref observer) = self.retry_observer {
+                observer(&RetryEvent {
+                    url: url.as_str().to_owned(),
+                    retry,
+                    reason,
+                    wait,
+                });
+            }
+            thread::sleep(wait);
+        }
+    }
+
+    /// Base URL of Google Scholar, which every query is sent to.
+    /// `citation_url` and `versions_url` of papers got by this client are under it.
+    pub fn base_url(&self) -> &BaseUrl {
+        &self.base_url
+    }
+
+    /// Interface language of Google Scholar, which every page is requested and scraped in.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Search papers listed by `query`, up to `total_count` papers over pages.
+    /// `query` may be any paged query, such as `SearchQuery` or `RelatedQuery`.
+    pub fn search<Q>(&self, query: &Q, total_count: u32) -> Result<Vec<Paper>>
+    where
+        Q: PagedQuery + Clone + fmt::Display,
+    {
+        paginate::collect_papers(self, &mut query.clone(), total_count)
+    }
+
+    /// Get a paper whose citers are listed by `query`, with up to `total_count` citers.
+    /// `total_count` of 0 results in `ErrorKind::InvalidQuery`, without sending a request,
+    /// since the paper is scraped from a page of its citers.
+    pub fn citations(&self, query: &CitationQuery, total_count: u32) -> Result<Paper> {
+        if total_count == 0 {
+            return Err(ErrorKind::InvalidQuery.into());
+        }
+
+        let mut query = query.clone();
+        query.set_count(cmp::min(total_count, MAX_RESULT_COUNT));
+        let body = self.send(&query)?;
+        let doc = CitationDocument::from(&*body)
+            .with_base_url(&self.base_url)
+            .with_language(self.language);
+        let mut paper = doc.scrape_target_paper_with_citers()?;
+        let mut citers = paper.citers.take().unwrap_or_default();
+
+        // Collect the rest of citers from the following pages.
+        if citers.len() as u32 == query.get_count() && total_count > query.get_count() {
+            let rest_count = total_count - query.get_count();
+            query.set_start(query.get_start() + query.get_count());
+            citers.extend(paginate::collect_papers(self, &mut query, rest_count)?);
+        }
+
+        paper.citers = Some(citers);
+        Ok(paper)
+    }
+
+    /// Get a paper of the cluster specified by `query`.
+    pub fn cluster(&self, query: &ClusterQuery) -> Result<Paper> {
+        let body = self.send(query)?;
+        let doc = ClusterDocument::from(&*body)
+            .with_base_url(&self.base_url)
+            .with_language(self.language);
+        doc.scrape_target_paper()
+    }
+
+    /// Get versions of a paper listed by `query`, in one page.
+    pub fn versions(&self, query: &VersionsQuery) -> Result<Vec<Version>> {
+        let body = self.send(query)?;
+        let doc = VersionsDocument::from(&*body)
+            .with_base_url(&self.base_url)
+            .with_language(self.language);
+        doc.scrape_versions()
+    }
+
+    /// Get BibTeX entry of a paper of `cluster_id`, exported by Google Scholar.
+    pub fn bibtex(&self, cluster_id: u64) -> Result<BibtexEntry> {
+        let body = self.send(&CiteQuery::new(cluster_id))?;
+        let doc = CiteDocument::from(&*body)
+            .with_base_url(&self.base_url)
+            .with_language(self.language);
+        let links = doc.scrape_export_links()?;
+        let bibtex_url = links.bibtex.ok_or(ErrorKind::ResultNotFount)?;
+        let bib = self.send(&ExportQuery::new(&bibtex_url))?;
+        scrape::scrape_bibtex(cluster_id, &bib)
+    }
+
+    /// Get an author profile with up to `publication_count` publications.
+    /// `publication_count` of 0 results in `ErrorKind::InvalidQuery`, without sending a request.
+    pub fn author_profile(
+        &self,
+        query: &AuthorProfileQuery,
+        publication_count: u32,
+    ) -> Result<AuthorProfile> {
+        paginate::collect_author_profile(self, &mut query.clone(), publication_count)
+    }
+
+    /// Search authors by name.
+    pub fn search_authors(&self, query: &AuthorSearchQuery) -> Result<Vec<AuthorSummary>> {
+        let body = self.send(query)?;
+        let doc = AuthorSearchDocument::from(&*body)
+            .with_base_url(&self.base_url)
+            .with_language(self.language);
+        doc.scrape_authors()
+    }
+}
+
+enum Outcome {
+    Body(String),
+    Retry(RetryReason, Error),
+    Fail(Error),
+}
+
+fn check_response(response: Response, language: Language) -> Outcome {
+    // Google Scholar shows the block page with various statuses, such as 429 and 503.
+    let Response { status, body } = response;
+    let is_blocked = || {
+        SearchDocument::from(&*body)
+            .with_language(language)
+            .is_blocked()
+    };
+    if status == 429 || is_blocked() {
+        return Outcome::Retry(RetryReason::Blocked, ErrorKind::Blocked.into());
+    }
+
+    match status {
+        200..=299 => Outcome::Body(body),
+        500..=599 => Outcome::Retry(
+            RetryReason::HttpStatus(status),
+            ErrorKind::HttpStatus(status).into(),
+        ),
+        _ => Outcome::Fail(ErrorKind::HttpStatus(status).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_util::{test_client, TestDir};
+    use transport::MemoryTransport;
+
+    #[test]
+    fn check_response_test() {
+        use std::fs::File;
+        use std::io::Read;
+
+        let blocked_body = {
+            let mut s = String::new();
+            let mut file = File::open("src/test_html/blocked.html").unwrap();
+            file.read_to_string(&mut s).unwrap();
+            s
+        };
+
+        let response = |status, body: &str| Response {
+            status,
+            body: body.to_owned(),
+        };
+
+        match check_response(response(200, "<html></html>"), Language::English) {
+            Outcome::Body(body) => assert_eq!(body, "<html></html>"),
+            _ => panic!("not a body"),
+        }
+        match check_response(response(503, &blocked_body), Language::English) {
+            Outcome::Retry(RetryReason::Blocked, _) => {}
+            _ => panic!("not blocked"),
+        }
+        match check_response(response(429, ""), Language::English) {
+            Outcome::Retry(RetryReason::Blocked, _) => {}
+            _ => panic!("not blocked"),
+        }
+        match check_response(response(502, ""), Language::English) {
+            Outcome::Retry(RetryReason::HttpStatus(502), _) => {}
+            _ => panic!("not retried"),
+        }
+        match check_response(response(404, ""), Language::English) {
+            Outcome::Fail(_) => {}
+            _ => panic!("not failed"),
+        }
+    }
+
+    #[test]
+    fn send_retry_test() {
+        use std::sync::Mutex;
+
+        use request::ClusterQuery;
+        use request::Query;
+        use retry::BlockedAction;
+
+        let query = ClusterQuery::new(5545735591029960915);
+        let url = query.to_url().unwrap();
+
+        let retry = RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_secs(0),
+            on_blocked: BlockedAction::Wait(Duration::from_secs(0)),
+            ..RetryPolicy::default()
+        };
+
+        // Recovered from a server error and a block.
+        let transport = {
+            let mut t = MemoryTransport::new();
+            t.insert(
+                &url,
+                Response {
+                    status: 503,
+                    body: String::new(),
+                },
+            );
+            t.insert(
+                &url,
+                Response {
+                    status: 429,
+                    body: String::new(),
+                },
+            );
+            t.insert(&url, Response::ok("<html></html>"));
+            Arc::new(t)
+        };
+        let mut client = test_client(transport.clone(), retry.clone());
+        let events = Arc::new(Mutex::new(Vec::new()));
+        {
+            let events = events.clone();
+            client.set_retry_observer(move |e| events.lock().unwrap().push(e.reason.clone()));
+        }
+
+        assert_eq!(client.send(&query).unwrap(), "<html></html>");
+        assert_eq!(transport.requested_urls().len(), 3);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![RetryReason::HttpStatus(503), RetryReason::Blocked]
+        );
+
+        // Blocked, with no retry.
+        let transport = {
+            let mut t = MemoryTransport::new();
+            t.insert(
+                &url,
+                Response {
+                    status: 429,
+                    body: String::new(),
+                },
+            );
+            Arc::new(t)
+        };
+        let client = test_client(transport.clone(), RetryPolicy::default());
+        match client.send(&query) {
+            Err(Error(ErrorKind::Blocked, _)) => {}
+            _ => panic!("not blocked"),
+        }
+        assert_eq!(transport.requested_urls().len(), 1);
+
+        // Not found, never retried.
+        let client = test_client(Arc::new(MemoryTransport::new()), retry);
+        match client.send(&query) {
+            Err(Error(ErrorKind::HttpStatus(404), _)) => {}
+            _ => panic!("not failed"),
+        }
+    }
+
+    #[test]
+    fn send_replay_mismatch_test() {
+        use std::sync::Mutex;
+
+        use archive::{RecordingTransport, ReplayTransport};
+        use request::ClusterQuery;
+
+        // An empty archive.
+        let dir = TestDir::new("client-replay");
+        RecordingTransport::new(MemoryTransport::new(), &*dir).unwrap();
+        let transport = ReplayTransport::load(&*dir).unwrap();
+
+        let mut client = ScholarClient::with_transport(
+            ClientConfig {
+                rate_limit: RateLimit::unlimited(),
+                ..ClientConfig::default()
+            },
+            transport,
+        );
+        let retried = Arc::new(Mutex::new(false));
+        {
+            let retried = retried.clone();
+            client.set_retry_observer(move |_| *retried.lock().unwrap() = true);
+        }
+
+        match client.send(&ClusterQuery::new(42)) {
+            Err(Error(ErrorKind::ReplayMismatch(_), _)) => {}
+            _ => panic!("not mismatched"),
+        }
+        assert!(!*retried.lock().unwrap());
+    }
+
+    #[test]
+    fn send_cache_test() {
+        use request::ClusterQuery;
+        use request::Query;
+
+        let dir = TestDir::new("client-cache");
+
+        let query = ClusterQuery::new(5545735591029960915);
+        let url = query.to_url().unwrap();
+
+        let cache_client = |transport, offline| {
+            ScholarClient::with_transport(
+                ClientConfig {
+                    rate_limit: RateLimit::unlimited(),
+                    cache: Some(CacheConfig {
+                        offline,
+                        ..CacheConfig::new(&*dir)
+                    }),
+                    ..ClientConfig::default()
+                },
+                transport,
+            )
+        };
+
+        // Not cached yet.
+        let client = cache_client(Arc::new(MemoryTransport::new()), true);
+        match client.send(&query) {
+            Err(Error(ErrorKind::CacheMiss(ref u), _)) if *u == url.as_str() => {}
+            _ => panic!("not missed"),
+        }
+
+        let transport = {
+            let mut t = MemoryTransport::new();
+            t.insert(&url, Response::ok("<html></html>"));
+            Arc::new(t)
+        };
+        let client = cache_client(transport.clone(), false);
+        assert_eq!(client.send(&query).unwrap(), "<html></html>");
+        assert_eq!(client.send(&query).unwrap(), "<html></html>");
+        assert_eq!(transport.requested_urls().len(), 1);
+
+        let client = cache_client(Arc::new(MemoryTransport::new()), true);
+        assert_eq!(client.send(&query).unwrap(), "<html></html>");
+    }
+
+    #[test]
+    fn base_url_test() {
+        use request::ClusterQuery;
+
+        let base_url = BaseUrl::new("http://localhost:8080/").unwrap();
+        let query = ClusterQuery::new(5545735591029960915);
+        let url = query.to_url_with_base(&base_url).unwrap();
+
+        let transport = {
+            let mut t = MemoryTransport::new();
+            t.insert_file(&url, "src/test_html/quantum_theory_cluster.html")
+                .unwrap();
+            Arc::new(t)
+        };
+        let client = ScholarClient::with_transport(
+            ClientConfig {
+                rate_limit: RateLimit::unlimited(),
+                base_url,
+                ..ClientConfig::default()
+            },
+            transport.clone(),
+        );
+
+        let paper = client.cluster(&query).unwrap();
+        assert_eq!(
+            transport.requested_urls(),
+            vec![String::from(
+                "http://localhost:8080/scholar?cluster=5545735591029960915&hl=en",
+            )]
+        );
+        assert_eq!(
+            paper.citation_url,
+            format!("http://localhost:8080/scholar?cites={}", paper.cluster_id)
+        );
+    }
+
+    #[test]
+    fn language_test() {
+        use request::SearchQuery;
+
+        let mut query = SearchQuery::default();
+        query.set_words("quantum theory");
+        query.set_count(10);
+        let url = query
+            .to_localized_url(&BaseUrl::default(), Language::Japanese)
+            .unwrap();
+        assert!(url.as_str().contains("&hl=ja&"));
+
+        let transport = {
+            let mut t = MemoryTransport::new();
+            t.insert_file(&url, "src/test_html/quantum_theory_ja.html")
+                .unwrap();
+            Arc::new(t)
+        };
+        let client = ScholarClient::with_transport(
+            ClientConfig {
+                rate_limit: RateLimit::unlimited(),
+                language: Language::Japanese,
+                ..ClientConfig::default()
+            },
+            transport.clone(),
+        );
+
+        // The page is requested and scraped in Japanese.
+        let papers = client.search(&query, 10).unwrap();
+        assert_eq!(transport.requested_urls(), vec![url.into_string()]);
+        assert_eq!(papers.len(), 10);
+    }
+
+    #[test]
+    fn citations_pages_test() {
+        use request::{CitationQuery, Query};
+
+        let citation_url = |start: u32, count: u32| {
+            let mut query = CitationQuery::from_cluster_id(5545735591029960915, &BaseUrl::default());
+            query.set_start(start);
+            query.set_count(count);
+            query.to_url().unwrap()
+        };
+
+        let transport = {
+            let mut t = MemoryTransport::new();
+            for start in &[0, 10] {
+                t.insert_file(
+                    &citation_url(*start, 10),
+                    "src/test_html/quantum_theory_citations.html",
+                ).unwrap();
+            }
+            t.insert_file(&citation_url(20, 5), "src/test_html/uncited.html")
+                .unwrap();
+            Arc::new(t)
+        };
+        let client = test_client(transport.clone(), RetryPolicy::no_retry());
+
+        let query = CitationQuery::from_cluster_id(5545735591029960915, &BaseUrl::default());
+        let paper = client.citations(&query, 25).unwrap();
+        assert_eq!(
+            paper.title,
+            "Significance of electromagnetic potentials in the quantum theory"
+        );
+        assert_eq!(paper.citers.unwrap().len(), 23);
+        assert_eq!(
+            transport.requested_urls(),
+            vec![
+                citation_url(0, 10).to_string(),
+                citation_url(10, 10).to_string(),
+                citation_url(20, 5).to_string(),
+            ]
+        );
+
+        // Within the first page.
+        let mut query = CitationQuery::from_cluster_id(5545735591029960915, &BaseUrl::default());
+        query.set_count(10);
+        let paper = client.citations(&query, 10).unwrap();
+        assert_eq!(paper.citers.unwrap().len(), 10);
+        assert_eq!(transport.requested_urls().len(), 4);
+
+        // The query is left as it is.
+        assert_eq!(query.get_count(), 10);
+        assert_eq!(query.get_start(), 0);
+
+        // No request for no citers.
+        assert!(client.citations(&query, 0).is_err());
+        assert_eq!(transport.requested_urls().len(), 4);
+    }
+}
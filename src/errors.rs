@@ -5,17 +5,60 @@ error_chain!{
         Io(::std::io::Error);
         Reqwest(::reqwest::Error);
         Parse(::std::num::ParseIntError);
+        Serde(::serde_json::Error);
     }
 
     errors {
         BadHtml {
             description("Bad HTML structure")
         }
+        BadBibtex {
+            description("Bad BibTeX format")
+        }
         ResultNotFount {
             description("Result not found")
         }
         InvalidQuery {
             description("Invalid query")
         }
+        InvalidBaseUrl(url: String) {
+            description("Invalid base URL")
+            display("Invalid base URL: {}", url)
+        }
+        Blocked {
+            description("Request blocked")
+        }
+        HttpStatus(status: u16) {
+            description("Unexpected HTTP status")
+            display("Unexpected HTTP status: {}", status)
+        }
+        CacheMiss(url: String) {
+            description("Response not cached")
+            display("Response not cached in offline mode: {}", url)
+        }
+        ReplayMismatch(url: String) {
+            description("Request not recorded")
+            display("Request not recorded at this point of the archive: {}", url)
+        }
+        RequestBudgetExceeded(max_requests: u32) {
+            description("Request budget exceeded")
+            display("Request budget exceeded: {} requests at most", max_requests)
+        }
+    }
+}
+
+impl Error {
+    /// Whether the error is a network error or a timeout, which may not happen again
+    /// on a retry of the request.
+    pub fn is_transient(&self) -> bool {
+        match *self.kind() {
+            ErrorKind::Reqwest(ref e) => {
+                e.is_http()
+                    || e.get_ref()
+                        .and_then(|e| e.downcast_ref::<::std::io::Error>())
+                        .is_some()
+            }
+            _ => false,
+        }
     }
 }
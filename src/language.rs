@@ -0,0 +1,93 @@
+//! Interface languages of Google Scholar, and localized texts the scraper relies on.
+
+use std::fmt;
+
+/// Interface language of Google Scholar, sent as `hl` parameter.
+/// Pages are scraped by the texts of the language they are requested in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    Japanese,
+    German,
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+pub(crate) struct LocalizedTexts {
+    /// Part of the message on the page shown when Google Scholar blocked a request.
+    pub block_message: &'static str,
+    /// Words following the number of results, like "results" in "About 1,000 results".
+    pub result_words: &'static [&'static str],
+    /// Document type tags, like "BOOK" in "[BOOK]".
+    pub book_tag: &'static str,
+    pub citation_tag: &'static str,
+    /// Words preceding the verified email domain on author profiles, like "Verified email at".
+    pub email_words: &'static str,
+}
+
+const ENGLISH_TEXTS: LocalizedTexts = LocalizedTexts {
+    block_message: "Our systems have detected unusual traffic from your computer network.",
+    result_words: &["results", "result"],
+    book_tag: "BOOK",
+    citation_tag: "CITATION",
+    email_words: "Verified email at",
+};
+
+const JAPANESE_TEXTS: LocalizedTexts = LocalizedTexts {
+    block_message: "通常と異なるトラフィックが検出されました",
+    result_words: &["件"],
+    book_tag: "書籍",
+    citation_tag: "引用",
+    email_words: "確認したメール アドレス:",
+};
+
+const GERMAN_TEXTS: LocalizedTexts = LocalizedTexts {
+    block_message: "ungewöhnlichen Datenverkehr aus Ihrem Computernetzwerk festgestellt",
+    result_words: &["Ergebnissen", "Ergebnisse", "Ergebnis"],
+    book_tag: "BUCH",
+    citation_tag: "ZITATION",
+    email_words: "Bestätigte E-Mail-Adresse bei",
+};
+
+impl Language {
+    /// All supported languages.
+    pub fn all() -> &'static [Language] {
+        &[Language::English, Language::Japanese, Language::German]
+    }
+
+    /// Language code, like "en".
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Japanese => "ja",
+            Language::German => "de",
+        }
+    }
+
+    /// Language of `code`, like "en". `None` if not supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::language::Language;
+    ///
+    /// assert_eq!(Language::from_code("ja"), Some(Language::Japanese));
+    /// assert_eq!(Language::from_code("xx"), None);
+    /// ```
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::all().iter().cloned().find(|lang| lang.code() == code)
+    }
+
+    pub(crate) fn texts(self) -> &'static LocalizedTexts {
+        match self {
+            Language::English => &ENGLISH_TEXTS,
+            Language::Japanese => &JAPANESE_TEXTS,
+            Language::German => &GERMAN_TEXTS,
+        }
+    }
+}
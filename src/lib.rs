@@ -4,18 +4,38 @@
 extern crate error_chain;
 #[macro_use]
 extern crate lazy_static;
+extern crate rand;
 extern crate regex;
 extern crate reqwest;
 extern crate select;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+extern crate serde_json;
 
+pub mod archive;
+pub mod author;
+pub mod cache;
+pub mod client;
 pub mod errors;
+pub mod language;
+pub mod paginate;
 pub mod paper;
+pub mod rate_limit;
+mod related_id;
 pub mod request;
+pub mod retry;
 pub mod scrape;
+#[cfg(test)]
+mod test_util;
+pub mod transport;
 
-const GOOGLESCHOLAR_URL_BASE: &str = "https://scholar.google.com/scholar";
+const GOOGLESCHOLAR_ROOT_URL: &str = "https://scholar.google.com/";
 
 pub const MAX_RESULT_COUNT: u32 = 10;
+
+/// Google Scholar does not list results beyond this number, however many pages are requested.
+pub const MAX_TOTAL_RESULT_COUNT: u32 = 1000;
+
+/// Maximum number of publications listed in one page of an author profile.
+pub const MAX_PUBLICATION_PAGE_SIZE: u32 = 100;
@@ -0,0 +1,290 @@
+//! Send successive requests to collect papers and publications listed over multiple pages.
+
+use std::cmp;
+use std::fmt;
+
+
+use super::{MAX_RESULT_COUNT, MAX_TOTAL_RESULT_COUNT};
+use author::AuthorProfile;
+use client::ScholarClient;
+use errors::*;
+use paper::Paper;
+use request::{AuthorProfileQuery, PagedQuery};
+use scrape::{AuthorProfileDocument, PapersDocument, SearchDocument};
+
+/// Collects up to `total_count` papers listed by `query`,
+/// requesting pages one by one from the current offset of `query`.
+///
+/// Pages are requested until `total_count` papers are collected or the results run out.
+/// `total_count` will be rounded down to `MAX_TOTAL_RESULT_COUNT`.
+/// The offset and count of `query` are overwritten while requesting pages.
+///
+/// # Return value
+///
+/// `Ok` of concatenated papers, or `Error`.
+/// `ErrorKind::Blocked` is returned if Google Scholar blocked any of the requests.
+pub fn collect_papers<Q>(client: &ScholarClient, query: &mut Q, total_count: u32) -> Result<Vec<Paper>>
+where
+    Q: PagedQuery + fmt::Display,
+{
+    let total_count = cmp::min(total_count, MAX_TOTAL_RESULT_COUNT);
+    let first_start = query.get_start();
+
+    let mut papers = Vec::with_capacity(total_count as usize);
+    while (papers.len() as u32) < total_count {
+        let page_count = cmp::min(total_count - papers.len() as u32, MAX_RESULT_COUNT);
+        query.set_start(first_start + papers.len() as u32);
+        query.set_count(page_count);
+
+        let body = client.send(query)?;
+        // Pages of any paged query list papers as search results do.
+        let doc = SearchDocument::from(&*body)
+            .with_base_url(client.base_url())
+            .with_language(client.language());
+
+        let page = doc.scrape_papers()?;
+        let is_last_page = (page.len() as u32) < page_count;
+        papers.extend(page);
+
+        if is_last_page {
+            break;
+        }
+    }
+
+    Ok(papers)
+}
+
+/// Get an author profile with up to `publication_count` publications,
+/// requesting pages of publications one by one from the current offset of `query`.
+/// Each page lists up to `MAX_PUBLICATION_PAGE_SIZE` publications.
+///
+/// The offset and page size of `query` are overwritten while requesting pages.
+/// `publication_count` of 0 results in `ErrorKind::InvalidQuery`, without sending a request.
+///
+/// # Return value
+///
+/// `Ok` of the profile, or `Error`.
+/// `ErrorKind::Blocked` is returned if Google Scholar blocked any of the requests.
+pub fn collect_author_profile(
+    client: &ScholarClient,
+    query: &mut AuthorProfileQuery,
+    publication_count: u32,
+) -> Result<AuthorProfile> {
+    if publication_count == 0 {
+        return Err(ErrorKind::InvalidQuery.into());
+    }
+
+    let first_start = query.get_start();
+    query.set_page_size(publication_count);
+
+    let mut profile = request_author_profile_page(client, query)?.scrape_profile()?;
+    profile.publications.truncate(publication_count as usize);
+    let mut is_last_page = (profile.publications.len() as u32) < query.get_page_size();
+
+    while !is_last_page && (profile.publications.len() as u32) < publication_count {
+        let collected = profile.publications.len() as u32;
+        query.set_start(first_start + collected);
+        query.set_page_size(publication_count - collected);
+
+        let page = request_author_profile_page(client, query)?.scrape_publications()?;
+        is_last_page = (page.len() as u32) < query.get_page_size();
+        profile.publications.extend(page);
+    }
+
+    Ok(profile)
+}
+
+fn request_author_profile_page(
+    client: &ScholarClient,
+    query: &AuthorProfileQuery,
+) -> Result<AuthorProfileDocument> {
+    let body = client.send(query)?;
+    let doc = AuthorProfileDocument::from(&*body)
+        .with_base_url(client.base_url())
+        .with_language(client.language());
+    Ok(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::Arc;
+
+    use reqwest::Url;
+
+    use super::*;
+    use MAX_PUBLICATION_PAGE_SIZE;
+    use request::{Query, SearchQuery};
+    use retry::RetryPolicy;
+    use test_util::test_client;
+    use transport::{MemoryTransport, Response};
+
+    const FULL_PAGE: &str = "src/test_html/quantum_theory_citations.html"; // 10 papers
+    const SHORT_PAGE: &str = "src/test_html/uncited.html"; // 3 papers
+
+    fn search_query() -> SearchQuery {
+        let mut query = SearchQuery::default();
+        query.set_words("quantum");
+        query
+    }
+
+    fn search_url(start: u32, count: u32) -> Url {
+        let mut query = search_query();
+        query.set_start(start);
+        query.set_count(count);
+        query.to_url().unwrap()
+    }
+
+    fn collect(pages: &[(Url, &str)], total_count: u32) -> (Vec<Paper>, Vec<String>) {
+        let transport = {
+            let mut t = MemoryTransport::new();
+            for &(ref url, path) in pages {
+                t.insert_file(url, path).unwrap();
+            }
+            Arc::new(t)
+        };
+        let client = test_client(transport.clone(), RetryPolicy::default());
+
+        let papers = collect_papers(&client, &mut search_query(), total_count).unwrap();
+        (papers, transport.requested_urls())
+    }
+
+    #[test]
+    fn collect_papers_full_pages_test() {
+        let (papers, requested_urls) = collect(
+            &[
+                (search_url(0, 10), FULL_PAGE),
+                (search_url(10, 10), FULL_PAGE),
+            ],
+            20,
+        );
+
+        assert_eq!(papers.len(), 20);
+        assert_eq!(
+            requested_urls,
+            vec![search_url(0, 10).to_string(), search_url(10, 10).to_string()]
+        );
+    }
+
+    #[test]
+    fn collect_papers_short_last_page_test() {
+        let (papers, requested_urls) = collect(
+            &[
+                (search_url(0, 10), FULL_PAGE),
+                (search_url(10, 10), FULL_PAGE),
+                (search_url(20, 10), SHORT_PAGE),
+            ],
+            40,
+        );
+
+        // Results ran out on the third page.
+        assert_eq!(papers.len(), 23);
+        assert_eq!(requested_urls.len(), 3);
+    }
+
+    #[test]
+    fn collect_papers_partial_page_test() {
+        let (papers, requested_urls) = collect(
+            &[
+                (search_url(0, 10), FULL_PAGE),
+                (search_url(10, 5), SHORT_PAGE),
+            ],
+            15,
+        );
+
+        assert_eq!(papers.len(), 13);
+        assert_eq!(
+            requested_urls,
+            vec![search_url(0, 10).to_string(), search_url(10, 5).to_string()]
+        );
+
+        // Less than a page.
+        let (papers, requested_urls) = collect(&[(search_url(0, 3), SHORT_PAGE)], 3);
+        assert_eq!(papers.len(), 3);
+        assert_eq!(requested_urls, vec![search_url(0, 3).to_string()]);
+    }
+
+    #[test]
+    fn collect_papers_blocked_test() {
+        let (url, blocked) = (search_url(10, 10), "src/test_html/blocked.html");
+        let transport = {
+            let mut t = MemoryTransport::new();
+            t.insert_file(&search_url(0, 10), FULL_PAGE).unwrap();
+            t.insert_file(&url, blocked).unwrap();
+            Arc::new(t)
+        };
+        let client = test_client(transport, RetryPolicy::default());
+
+        match collect_papers(&client, &mut search_query(), 20) {
+            Err(Error(ErrorKind::Blocked, _)) => {}
+            _ => panic!("not blocked"),
+        }
+    }
+
+    // Author profile page listing `count` publications, repeating the ones in the fixture.
+    fn author_profile_page(count: usize) -> String {
+        let page = fs::read_to_string("src/test_html/author_profile.html").unwrap();
+        let rows_start = page.find(r#"<tr class="gsc_a_tr">"#).unwrap();
+        let rows_end = rows_start + page[rows_start..].find("</tbody>").unwrap();
+
+        let rows = page[rows_start..rows_end]
+            .split(r#"<tr class="gsc_a_tr">"#)
+            .filter(|row| !row.trim().is_empty())
+            .collect::<Vec<_>>();
+        let repeated = rows.iter()
+            .cycle()
+            .take(count)
+            .map(|row| format!(r#"<tr class="gsc_a_tr">{}"#, row))
+            .collect::<String>();
+
+        format!("{}{}{}", &page[..rows_start], repeated, &page[rows_end..])
+    }
+
+    fn author_profile_url(start: u32, page_size: u32) -> Url {
+        let mut query = AuthorProfileQuery::new("lpTL7HwAAAAJ");
+        query.set_start(start);
+        query.set_page_size(page_size);
+        query.to_url().unwrap()
+    }
+
+    #[test]
+    fn collect_author_profile_test() {
+        let transport = {
+            let mut t = MemoryTransport::new();
+            t.insert(
+                &author_profile_url(0, MAX_PUBLICATION_PAGE_SIZE),
+                Response::ok(&author_profile_page(MAX_PUBLICATION_PAGE_SIZE as usize)),
+            );
+            t.insert(
+                &author_profile_url(MAX_PUBLICATION_PAGE_SIZE, 50),
+                Response::ok(&author_profile_page(3)),
+            );
+            t.insert_file(&author_profile_url(0, 2), "src/test_html/author_profile.html")
+                .unwrap();
+            Arc::new(t)
+        };
+        let client = test_client(transport.clone(), RetryPolicy::default());
+
+        let mut query = AuthorProfileQuery::new("lpTL7HwAAAAJ");
+        let profile = collect_author_profile(&client, &mut query, 150).unwrap();
+
+        // Publications ran out on the second page.
+        assert_eq!(profile.publications.len(), 103);
+        assert_eq!(
+            profile.publications[100].title,
+            "Quantum field theory and critical phenomena"
+        );
+        assert_eq!(transport.requested_urls().len(), 2);
+
+        // Truncated to the requested count.
+        let mut query = AuthorProfileQuery::new("lpTL7HwAAAAJ");
+        let profile = collect_author_profile(&client, &mut query, 2).unwrap();
+        assert_eq!(profile.publications.len(), 2);
+        assert_eq!(transport.requested_urls().len(), 3);
+
+        // No request for no publications.
+        let mut query = AuthorProfileQuery::new("lpTL7HwAAAAJ");
+        assert!(collect_author_profile(&client, &mut query, 0).is_err());
+        assert_eq!(transport.requested_urls().len(), 3);
+    }
+}
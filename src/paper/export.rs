@@ -0,0 +1,569 @@
+//! Serialize `Paper`s into bibliography formats: BibTeX, RIS and CSL-JSON.
+//!
+//! Each serializer takes a list of papers and also exports their citers, recursively.
+//! A paper appearing more than once (e.g. a paper citing two of the listed ones) is exported
+//! only at its first appearance.
+
+use std::collections::HashSet;
+
+use serde_json;
+
+use super::{Paper, PaperKind};
+use errors::*;
+
+/// Flatten `papers` and their citers, recursively, into a list without duplicates.
+/// Papers are identified by their cluster IDs.
+pub fn flatten(papers: &[Paper]) -> Vec<&Paper> {
+    fn flatten_rec<'a>(papers: &'a [Paper], seen: &mut HashSet<u64>, flat: &mut Vec<&'a Paper>) {
+        for p in papers {
+            if seen.insert(p.cluster_id) {
+                flat.push(p);
+            }
+            if let Some(ref citers) = p.citers {
+                flatten_rec(citers, seen, flat);
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut flat = vec![];
+    flatten_rec(papers, &mut seen, &mut flat);
+    flat
+}
+
+/// Generate a citation key from family name of the first author, published year and
+/// the first significant word of title, like `zinn1996quantum`.
+/// Latin letters with diacritics are transliterated into ASCII, like "Émile" into `emile`.
+/// If the family name has no Latin letters, like "田中", the key is made of the cluster ID
+/// instead, like `scholar42`.
+///
+/// # Example
+///
+/// ```
+/// use scholar::paper::{Author, Paper};
+/// use scholar::paper::export::citation_key;
+///
+/// let mut paper = Paper::new("The quantum theory of solids", 0);
+/// paper.authors = vec![Author { name: String::from("C Kittel"), user_id: None }];
+/// paper.year = Some(1963);
+/// assert_eq!(citation_key(&paper), "kittel1963quantum");
+///
+/// paper.authors = vec![Author { name: String::from("É Schrödinger"), user_id: None }];
+/// assert_eq!(citation_key(&paper), "schrodinger1963quantum");
+/// ```
+pub fn citation_key(paper: &Paper) -> String {
+    const STOP_WORDS: &[&str] = &[
+        "a", "an", "and", "at", "by", "for", "from", "in", "of", "on", "the", "to", "with",
+    ];
+
+    let author = match paper.authors.first() {
+        Some(a) => key_word(split_name(&a.name).0),
+        None => String::from("anonymous"),
+    };
+    if author.is_empty() {
+        return format!("scholar{}", paper.cluster_id);
+    }
+
+    let year = paper.year.map(|y| y.to_string()).unwrap_or_default();
+
+    let title_word = paper
+        .title
+        .split_whitespace()
+        .map(key_word)
+        .find(|w| !w.is_empty() && !STOP_WORDS.contains(&w.as_str()))
+        .unwrap_or_default();
+
+    format!("{}{}{}", author, year, title_word)
+}
+
+/// Serialize papers and their citers into BibTeX entries.
+pub fn to_bibtex(papers: &[Paper]) -> String {
+    let papers = flatten(papers);
+    let keys = unique_citation_keys(&papers);
+
+    let entries = papers
+        .iter()
+        .zip(keys)
+        .map(|(p, key)| {
+            let entry_type = match EntryType::of(p) {
+                EntryType::Article => "article",
+                EntryType::Book => "book",
+                EntryType::Misc => "misc",
+            };
+
+            let mut fields = vec![("title", escape_bibtex(&p.title))];
+            if !p.authors.is_empty() {
+                let authors = p.authors
+                    .iter()
+                    .map(|a| escape_bibtex(&bibtex_name(&a.name)))
+                    .collect::<Vec<_>>();
+                fields.push(("author", authors.join(" and ")));
+            }
+            if let Some(ref venue) = p.venue {
+                let name = match EntryType::of(p) {
+                    EntryType::Article => "journal",
+                    _ => "howpublished",
+                };
+                fields.push((name, escape_bibtex(venue)));
+            }
+            if let Some(year) = p.year {
+                fields.push(("year", year.to_string()));
+            }
+            if let Some(ref publisher) = p.publisher {
+                fields.push(("publisher", escape_bibtex(publisher)));
+            }
+            if let Some(ref link) = p.link {
+                fields.push(("url", escape_bibtex_url(link)));
+            }
+
+            let fields = fields
+                .iter()
+                .map(|f| format!("  {}={{{}}}", f.0, f.1))
+                .collect::<Vec<_>>();
+            format!("@{}{{{},\n{}\n}}\n", entry_type, key, fields.join(",\n"))
+        })
+        .collect::<Vec<_>>();
+
+    entries.join("\n")
+}
+
+/// Serialize papers and their citers into RIS records.
+pub fn to_ris(papers: &[Paper]) -> String {
+    let papers = flatten(papers);
+    let keys = unique_citation_keys(&papers);
+
+    papers
+        .iter()
+        .zip(keys)
+        .map(|(p, key)| {
+            let ty = match EntryType::of(p) {
+                EntryType::Article => "JOUR",
+                EntryType::Book => "BOOK",
+                EntryType::Misc => "GEN",
+            };
+
+            let mut tags = vec![("TY", String::from(ty)), ("ID", key)];
+            tags.push(("TI", escape_ris(&p.title)));
+            for a in &p.authors {
+                tags.push(("AU", escape_ris(&bibtex_name(&a.name))));
+            }
+            if let Some(ref venue) = p.venue {
+                tags.push(("JO", escape_ris(venue)));
+            }
+            if let Some(year) = p.year {
+                tags.push(("PY", year.to_string()));
+            }
+            if let Some(ref publisher) = p.publisher {
+                tags.push(("PB", escape_ris(publisher)));
+            }
+            if let Some(ref link) = p.link {
+                tags.push(("UR", escape_ris(link)));
+            }
+            tags.push(("ER", String::new()));
+
+            tags.iter()
+                .map(|t| format!("{}  - {}\n", t.0, t.1))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Serialize papers and their citers into a CSL-JSON array.
+pub fn to_csl_json(papers: &[Paper]) -> Result<String> {
+    let papers = flatten(papers);
+    let keys = unique_citation_keys(&papers);
+
+    let items = papers
+        .iter()
+        .zip(keys)
+        .map(|(p, id)| CslItem {
+            id,
+            kind: match EntryType::of(p) {
+                EntryType::Article => "article-journal",
+                EntryType::Book => "book",
+                EntryType::Misc => "article",
+            },
+            title: p.title.clone(),
+            author: p.authors
+                .iter()
+                .map(|a| {
+                    let (family, given) = split_name(&a.name);
+                    CslName {
+                        family: family.to_owned(),
+                        given: given.map(ToOwned::to_owned),
+                    }
+                })
+                .collect(),
+            container_title: p.venue.clone(),
+            issued: p.year.map(|y| CslDate {
+                date_parts: vec![vec![y]],
+            }),
+            publisher: p.publisher.clone(),
+            url: p.link.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    Ok(serde_json::to_string_pretty(&items)?)
+}
+
+#[derive(Serialize)]
+struct CslItem {
+    id: String,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    title: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    author: Vec<CslName>,
+    #[serde(rename = "container-title", skip_serializing_if = "Option::is_none")]
+    container_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issued: Option<CslDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    publisher: Option<String>,
+    #[serde(rename = "URL", skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+#[derive(Serialize)]
+struct CslName {
+    family: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    given: Option<String>,
+}
+
+#[derive(Serialize)]
+struct CslDate {
+    #[serde(rename = "date-parts")]
+    date_parts: Vec<Vec<u32>>,
+}
+
+enum EntryType {
+    Article,
+    Book,
+    Misc,
+}
+
+impl EntryType {
+    fn of(paper: &Paper) -> Self {
+        match paper.kind {
+            Some(PaperKind::Book) => EntryType::Book,
+            _ if paper.venue.is_some() => EntryType::Article,
+            _ => EntryType::Misc,
+        }
+    }
+}
+
+// Citation keys of `papers`, where duplicated keys are suffixed with
+// 'a', 'b', ..., 'z', 'aa', 'ab', ... in order.
+fn unique_citation_keys(papers: &[&Paper]) -> Vec<String> {
+    let mut used = HashSet::new();
+
+    papers
+        .iter()
+        .map(|p| {
+            let key = citation_key(p);
+            let mut unique_key = key.clone();
+            let mut suffix_index = 0;
+            while used.contains(&unique_key) {
+                unique_key = format!("{}{}", key, alphabetic_suffix(suffix_index));
+                suffix_index += 1;
+            }
+            used.insert(unique_key.clone());
+            unique_key
+        })
+        .collect()
+}
+
+// 0 -> "a", 25 -> "z", 26 -> "aa", 27 -> "ab", ...
+fn alphabetic_suffix(index: u32) -> String {
+    let mut suffix = Vec::new();
+    let mut n = index + 1;
+    while n > 0 {
+        n -= 1;
+        suffix.push(b'a' + (n % 26) as u8);
+        n /= 26;
+    }
+    suffix.reverse();
+    String::from_utf8(suffix).unwrap()
+}
+
+// Lowercase ASCII alphanumerics from the head of a word, until any other character.
+// Latin letters with diacritics are transliterated beforehand.
+fn key_word(word: &str) -> String {
+    word.chars()
+        .flat_map(|c| match transliterate_latin(c) {
+            Some(ascii) => ascii.chars().collect::<Vec<_>>(),
+            None => vec![c],
+        })
+        .take_while(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_lowercase()
+}
+
+// Lowercase ASCII of a letter in Latin-1 Supplement or Latin Extended-A, like 'É' -> "e".
+fn transliterate_latin(c: char) -> Option<&'static str> {
+    let ascii = match c {
+        'À'..='Å' | 'à'..='å' | '\u{100}'..='\u{105}' => "a",
+        'Æ' | 'æ' => "ae",
+        'Ç' | 'ç' | '\u{106}'..='\u{10d}' => "c",
+        'Ð' | 'ð' | '\u{10e}'..='\u{111}' => "d",
+        'È'..='Ë' | 'è'..='ë' | '\u{112}'..='\u{11b}' => "e",
+        '\u{11c}'..='\u{123}' => "g",
+        '\u{124}'..='\u{127}' => "h",
+        'Ì'..='Ï' | 'ì'..='ï' | '\u{128}'..='\u{131}' => "i",
+        '\u{132}' | '\u{133}' => "ij",
+        '\u{134}' | '\u{135}' => "j",
+        '\u{136}'..='\u{138}' => "k",
+        '\u{139}'..='\u{142}' => "l",
+        'Ñ' | 'ñ' | '\u{143}'..='\u{14b}' => "n",
+        'Ò'..='Ö' | 'Ø' | 'ò'..='ö' | 'ø' | '\u{14c}'..='\u{151}' => "o",
+        '\u{152}' | '\u{153}' => "oe",
+        '\u{154}'..='\u{159}' => "r",
+        'ß' => "ss",
+        '\u{15a}'..='\u{161}' | '\u{17f}' => "s",
+        '\u{162}'..='\u{167}' => "t",
+        'Þ' | 'þ' => "th",
+        'Ù'..='Ü' | 'ù'..='ü' | '\u{168}'..='\u{173}' => "u",
+        '\u{174}' | '\u{175}' => "w",
+        'Ý' | 'ý' | 'ÿ' | '\u{176}'..='\u{178}' => "y",
+        '\u{179}'..='\u{17e}' => "z",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+// Split an author name shown by Google Scholar, like "J Zinn-Justin", into family name and
+// given names (usually initials).
+fn split_name(name: &str) -> (&str, Option<&str>) {
+    let name = name.trim();
+    match name.rfind(' ') {
+        Some(pos) => (&name[pos + 1..], Some(name[..pos].trim())),
+        None => (name, None),
+    }
+}
+
+// "J Zinn-Justin" -> "Zinn-Justin, J"
+fn bibtex_name(name: &str) -> String {
+    match split_name(name) {
+        (family, Some(given)) => format!("{}, {}", family, given),
+        (family, None) => family.to_owned(),
+    }
+}
+
+fn escape_bibtex(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str(r"\textbackslash{}"),
+            '~' => escaped.push_str(r"\textasciitilde{}"),
+            '^' => escaped.push_str(r"\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' | '\r' => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// URL is typeset verbatim by the `url` package, but still must keep braces balanced and
+// not start a comment.
+fn escape_bibtex_url(url: &str) -> String {
+    let mut escaped = String::with_capacity(url.len());
+    for c in url.chars() {
+        if let '%' | '{' | '}' = c {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// RIS has no escape sequence; a value must just fit in one line.
+fn escape_ris(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paper::Author;
+
+    fn sample_papers() -> Vec<Paper> {
+        let mut citer = Paper::new("On the theory of #1 & 100% {braces}", 2);
+        citer.authors = vec![Author {
+            name: String::from("A Foo"),
+            user_id: None,
+        }];
+        citer.venue = Some(String::from("Journal of A_B"));
+        citer.year = Some(2000);
+        citer.publisher = Some(String::from("Elsevier"));
+
+        let mut citer_dup = citer.clone();
+        citer_dup.cluster_id = 3;
+        citer_dup.title = String::from("Theory of\nfoo");
+
+        let mut paper = Paper::new("Quantum field theory and critical phenomena", 1);
+        paper.kind = Some(PaperKind::Book);
+        paper.authors = vec![Author {
+            name: String::from("J Zinn-Justin"),
+            user_id: Some(String::from("lpTL7HwAAAAJ")),
+        }];
+        paper.year = Some(1996);
+        paper.host = Some(String::from("cds.cern.ch"));
+        paper.link = Some(String::from("http://cds.cern.ch/record/2280881?ln=en&p=100%25"));
+        paper.citers = Some(vec![citer.clone(), citer_dup]);
+
+        vec![paper, citer]
+    }
+
+    #[test]
+    fn flatten_test() {
+        let papers = sample_papers();
+        let ids = flatten(&papers)
+            .iter()
+            .map(|p| p.cluster_id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn citation_key_test() {
+        let papers = sample_papers();
+        assert_eq!(citation_key(&papers[0]), "zinn1996quantum");
+        assert_eq!(citation_key(&papers[1]), "foo2000theory");
+        assert_eq!(citation_key(&Paper::new("", 0)), "anonymous");
+
+        let key_of = |name: &str, title: &str| {
+            let mut paper = Paper::new(title, 42);
+            paper.authors = vec![Author {
+                name: name.to_owned(),
+                user_id: None,
+            }];
+            paper.year = Some(1905);
+            citation_key(&paper)
+        };
+        assert_eq!(key_of("Émile Borel", "Über die Theorie"), "borel1905uber");
+        assert_eq!(key_of("E Borel", "Éléments de la théorie"), "borel1905elements");
+        assert_eq!(key_of("Ł Łukasiewicz", "Logika"), "lukasiewicz1905logika");
+        assert_eq!(key_of("A Weiß", "Die Straße"), "weiss1905die");
+        assert_eq!(key_of("田中", "量子論"), "scholar42");
+
+        let flat = flatten(&papers);
+        assert_eq!(
+            unique_citation_keys(&flat),
+            vec!["zinn1996quantum", "foo2000theory", "foo2000theorya"]
+        );
+    }
+
+    #[test]
+    fn unique_citation_keys_many_test() {
+        let paper = Paper::new("Theory", 0);
+        let papers = vec![&paper; 30];
+        let keys = unique_citation_keys(&papers);
+
+        assert_eq!(keys[0], "anonymoustheory");
+        assert_eq!(keys[1], "anonymoustheorya");
+        assert_eq!(keys[26], "anonymoustheoryz");
+        assert_eq!(keys[27], "anonymoustheoryaa");
+        assert_eq!(keys[29], "anonymoustheoryac");
+        assert!(keys.iter().all(|k| k.chars().all(|c| c.is_ascii_alphanumeric())));
+        assert_eq!(keys.iter().collect::<HashSet<_>>().len(), 30);
+
+        assert_eq!(alphabetic_suffix(0), "a");
+        assert_eq!(alphabetic_suffix(25), "z");
+        assert_eq!(alphabetic_suffix(26), "aa");
+        assert_eq!(alphabetic_suffix(26 + 26 * 26), "aaa");
+    }
+
+    #[test]
+    fn to_bibtex_test() {
+        assert_eq!(
+            to_bibtex(&sample_papers()),
+            r#"@book{zinn1996quantum,
+  title={Quantum field theory and critical phenomena},
+  author={Zinn-Justin, J},
+  year={1996},
+  url={http://cds.cern.ch/record/2280881?ln=en&p=100\%25}
+}
+
+@article{foo2000theory,
+  title={On the theory of \#1 \& 100\% \{braces\}},
+  author={Foo, A},
+  journal={Journal of A\_B},
+  year={2000},
+  publisher={Elsevier}
+}
+
+@article{foo2000theorya,
+  title={Theory of foo},
+  author={Foo, A},
+  journal={Journal of A\_B},
+  year={2000},
+  publisher={Elsevier}
+}
+"#
+        );
+    }
+
+    #[test]
+    fn to_ris_test() {
+        let papers = sample_papers();
+        let expected = vec![
+            "TY  - BOOK",
+            "ID  - zinn1996quantum",
+            "TI  - Quantum field theory and critical phenomena",
+            "AU  - Zinn-Justin, J",
+            "PY  - 1996",
+            "UR  - http://cds.cern.ch/record/2280881?ln=en&p=100%25",
+            "ER  - ",
+            "",
+            "TY  - JOUR",
+            "ID  - foo2000theory",
+            "TI  - On the theory of #1 & 100% {braces}",
+            "AU  - Foo, A",
+            "JO  - Journal of A_B",
+            "PY  - 2000",
+            "PB  - Elsevier",
+            "ER  - ",
+            "",
+            "TY  - JOUR",
+            "ID  - foo2000theorya",
+            "TI  - Theory of foo",
+            "AU  - Foo, A",
+            "JO  - Journal of A_B",
+            "PY  - 2000",
+            "PB  - Elsevier",
+            "ER  - ",
+            "",
+        ];
+        assert_eq!(to_ris(&papers[..1]), expected.join("\n"));
+    }
+
+    #[test]
+    fn to_csl_json_test() {
+        let papers = sample_papers();
+        let json: serde_json::Value = serde_json::from_str(&to_csl_json(&papers).unwrap()).unwrap();
+
+        assert_eq!(json.as_array().unwrap().len(), 3);
+
+        assert_eq!(json[0]["id"], "zinn1996quantum");
+        assert_eq!(json[0]["type"], "book");
+        assert_eq!(json[0]["title"], "Quantum field theory and critical phenomena");
+        assert_eq!(json[0]["author"][0]["family"], "Zinn-Justin");
+        assert_eq!(json[0]["author"][0]["given"], "J");
+        assert_eq!(json[0]["issued"]["date-parts"][0][0], 1996);
+        assert!(json[0].get("publisher").is_none());
+        assert_eq!(json[0]["URL"], "http://cds.cern.ch/record/2280881?ln=en&p=100%25");
+        assert!(json[0].get("container-title").is_none());
+
+        assert_eq!(json[1]["type"], "article-journal");
+        assert_eq!(json[1]["container-title"], "Journal of A_B");
+        assert_eq!(json[1]["publisher"], "Elsevier");
+        assert_eq!(json[2]["id"], "foo2000theorya");
+        assert_eq!(json[2]["title"], "Theory of\nfoo");
+    }
+}
@@ -0,0 +1,354 @@
+//! `Paper` struct.
+
+use std::fmt;
+use std::borrow::Cow;
+
+use request::BaseUrl;
+
+pub mod export;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Paper {
+    pub title: String,
+    /// Link to PDF, HTML, etc.
+    pub link: Option<String>,
+    /// Document type tagged by Google Scholar.
+    /// `None` if the paper is not tagged.
+    pub kind: Option<PaperKind>,
+    /// Authors, possibly only some of them.
+    pub authors: Vec<Author>,
+    /// Journal, conference, etc.
+    pub venue: Option<String>,
+    /// Published year.
+    pub year: Option<u32>,
+    /// Publisher, if Google Scholar knows it.
+    pub publisher: Option<String>,
+    /// Domain hosting the paper, shown by Google Scholar in place of unknown publisher.
+    pub host: Option<String>,
+    /// Part of abstract shown in search results.
+    pub snippet: Option<String>,
+    /// Number of days since Google Scholar added the paper.
+    /// Shown only in results sorted by date.
+    pub days_ago: Option<u32>,
+    /// Links to full text, shown besides the search result.
+    pub full_text_links: Vec<FullTextLink>,
+    /// Cluster ID of paper.
+    pub cluster_id: u64,
+    pub citation_count: Option<u32>,
+    pub citers: Option<Vec<Paper>>,
+    /// URL of citation list page of Google Scholar.
+    pub citation_url: String,
+    /// Number of versions in the cluster.
+    pub version_count: Option<u32>,
+    /// URL of versions list page of Google Scholar.
+    pub versions_url: Option<String>,
+}
+
+/// Document type of a paper, such as `[BOOK]` or `[PDF]`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub enum PaperKind {
+    Book,
+    /// Only cited by other papers; Google Scholar does not have the paper itself.
+    Citation,
+    Html,
+    Pdf,
+    Other(String),
+}
+
+impl fmt::Display for PaperKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PaperKind::Book => write!(f, "[BOOK]"),
+            PaperKind::Citation => write!(f, "[CITATION]"),
+            PaperKind::Html => write!(f, "[HTML]"),
+            PaperKind::Pdf => write!(f, "[PDF]"),
+            PaperKind::Other(ref tag) => write!(f, "[{}]", tag),
+        }
+    }
+}
+
+/// Link to full text of a paper, possibly hosted other than the publisher.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FullTextLink {
+    pub url: String,
+    /// Domain hosting the full text.
+    pub source: String,
+    /// Format of the full text, such as `[PDF]` or `[HTML]`.
+    pub kind: Option<PaperKind>,
+}
+
+impl fmt::Display for FullTextLink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref kind) = self.kind {
+            write!(f, "{} ", kind)?;
+        }
+        write!(f, "{} ({})", self.source, self.url)
+    }
+}
+
+/// One of versions of a paper, listed in its cluster.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Version {
+    pub title: String,
+    /// Link to PDF, HTML, etc.
+    pub link: Option<String>,
+    /// Document type tagged by Google Scholar.
+    pub kind: Option<PaperKind>,
+    /// Journal, conference, etc.
+    pub venue: Option<String>,
+    /// Published year.
+    pub year: Option<u32>,
+    /// Publisher, if Google Scholar knows it.
+    pub publisher: Option<String>,
+    /// Domain hosting the version, shown by Google Scholar in place of unknown publisher.
+    pub host: Option<String>,
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            r#""{}"
+ Link to paper: {}
+ Document type: {}
+         Venue: {}
+Published year: {}
+     Publisher: {}
+          Host: {}"#,
+            self.title,
+            option_na(&self.link),
+            option_na(&self.kind),
+            option_na(&self.venue),
+            option_na(&self.year),
+            option_na(&self.publisher),
+            option_na(&self.host),
+        )
+    }
+}
+
+/// Author of a paper.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Author {
+    pub name: String,
+    /// User ID of Google Scholar profile, if linked.
+    pub user_id: Option<String>,
+}
+
+/// Links to citation export files of a paper, listed in the cite popup of Google Scholar.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ExportLinks {
+    pub bibtex: Option<String>,
+    pub endnote: Option<String>,
+    pub refman: Option<String>,
+    pub refworks: Option<String>,
+}
+
+/// BibTeX entry of a paper, exported by Google Scholar.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BibtexEntry {
+    /// Cluster ID of the paper.
+    pub cluster_id: u64,
+    /// Entry type such as `article` or `book`.
+    pub entry_type: String,
+    /// Citation key.
+    pub key: String,
+    /// Pairs of field name and value, in the order of appearance.
+    pub fields: Vec<(String, String)>,
+}
+
+impl BibtexEntry {
+    /// Get value of the field named `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|pair| pair.0 == name)
+            .map(|pair| pair.1.as_str())
+    }
+}
+
+impl fmt::Display for BibtexEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "@{}{{{},", self.entry_type, self.key)?;
+        for (i, field) in self.fields.iter().enumerate() {
+            let sep = if i + 1 < self.fields.len() { "," } else { "" };
+            write!(f, "\n  {}={{{}}}{}", field.0, field.1, sep)?;
+        }
+        write!(f, "\n}}")
+    }
+}
+
+impl fmt::Display for Paper {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let authors = if self.authors.is_empty() {
+            None
+        } else {
+            let names = self.authors
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<_>>();
+            Some(names.join(", "))
+        };
+
+        let full_text_links = if self.full_text_links.is_empty() {
+            None
+        } else {
+            let links = self.full_text_links
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            Some(links.join(", "))
+        };
+
+        write!(
+            f,
+            r#""{}"
+ Link to paper: {}
+ Document type: {}
+     Full text: {}
+       Authors: {}
+         Venue: {}
+Published year: {}
+     Publisher: {}
+          Host: {}
+         Added: {}
+    Cluster ID: {}
+Citation count: {}
+ Citation List: {}
+ Version count: {}
+ Versions List: {}
+       Snippet: {}"#,
+            self.title,
+            option_na(&self.link),
+            option_na(&self.kind),
+            option_na(&full_text_links),
+            option_na(&authors),
+            option_na(&self.venue),
+            option_na(&self.year),
+            option_na(&self.publisher),
+            option_na(&self.host),
+            option_na(&self.days_ago.map(|d| format!("{} days ago", d))),
+            self.cluster_id,
+            option_na(&self.citation_count.map(|u| u.to_string())),
+            self.citation_url,
+            option_na(&self.version_count),
+            option_na(&self.versions_url),
+            option_na(&self.snippet),
+        )
+    }
+}
+
+pub(crate) fn option_na<T: ToString>(c: &Option<T>) -> Cow<'static, str> {
+    match *c {
+        Some(ref c) => c.to_string().into(),
+        None => "N/A".into(),
+    }
+}
+
+impl Paper {
+    /// Create new `Paper` with specified `title` and `cluster_id`.
+    /// `citation_url` is set according to `cluster_id`.
+    /// `authors` and `full_text_links` are left empty, and other fields are left `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::paper::Paper;
+    ///
+    /// let paper = Paper::new("foo", 42);
+    /// assert_eq!(
+    ///     paper,
+    ///     Paper {
+    ///         title: String::from("foo"),
+    ///         link: None,
+    ///         kind: None,
+    ///         authors: vec![],
+    ///         venue: None,
+    ///         year: None,
+    ///         publisher: None,
+    ///         host: None,
+    ///         snippet: None,
+    ///         days_ago: None,
+    ///         full_text_links: vec![],
+    ///         cluster_id: 42,
+    ///         citation_count: None,
+    ///         citers: None,
+    ///         citation_url: format!("https://scholar.google.com/scholar?cites={}", 42),
+    ///         version_count: None,
+    ///         versions_url: None,
+    ///     });
+    /// ```
+    pub fn new(title: &str, cluster_id: u64) -> Self {
+        Self::with_base_url(title, cluster_id, &BaseUrl::default())
+    }
+
+    /// Same as `new`, with `citation_url` under `base` instead of scholar.google.com.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::paper::Paper;
+    /// use scholar::request::BaseUrl;
+    ///
+    /// let paper = Paper::with_base_url("foo", 42, &BaseUrl::regional("co.jp").unwrap());
+    /// assert_eq!(paper.citation_url, "https://scholar.google.co.jp/scholar?cites=42");
+    /// ```
+    pub fn with_base_url(title: &str, cluster_id: u64, base: &BaseUrl) -> Self {
+        let title = title.to_owned();
+        let citation_url = Self::cluster_id_to_citation_url(base, cluster_id);
+
+        Self {
+            title,
+            link: None,
+            kind: None,
+            authors: vec![],
+            venue: None,
+            year: None,
+            publisher: None,
+            host: None,
+            snippet: None,
+            days_ago: None,
+            full_text_links: vec![],
+            cluster_id,
+            citation_count: None,
+            citers: None,
+            citation_url,
+            version_count: None,
+            versions_url: None,
+        }
+    }
+
+    /// Move `citation_url` and `versions_url` under `base`, as well as those of citers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::paper::Paper;
+    /// use scholar::request::BaseUrl;
+    ///
+    /// let mut paper = Paper::new("foo", 42);
+    /// paper.rebase_urls(&BaseUrl::regional("co.jp").unwrap());
+    /// assert_eq!(paper.citation_url, "https://scholar.google.co.jp/scholar?cites=42");
+    /// ```
+    pub fn rebase_urls(&mut self, base: &BaseUrl) {
+        self.citation_url = base.rebase_str(&self.citation_url);
+        self.versions_url = self.versions_url.as_ref().map(|url| base.rebase_str(url));
+
+        if let Some(ref mut citers) = self.citers {
+            for citer in citers {
+                citer.rebase_urls(base);
+            }
+        }
+    }
+
+    fn cluster_id_to_citation_url(base: &BaseUrl, id: u64) -> String {
+        let mut url = base.scholar_url();
+        url.set_query(Some(&format!("cites={}", id)));
+        url.into_string()
+    }
+
+    pub(crate) fn cluster_id_to_versions_url(base: &BaseUrl, id: u64) -> String {
+        let mut url = base.scholar_url();
+        url.set_query(Some(&format!("cluster={}", id)));
+        url.into_string()
+    }
+}
@@ -0,0 +1,195 @@
+//! Pace requests to Google Scholar, which blocks frequent requests.
+
+use std::cmp;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::{self, Rng};
+
+use errors::*;
+
+/// Configuration of `RateLimiter`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Minimum interval between two successive requests.
+    pub min_interval: Duration,
+    /// Upper bound of random delay added to `min_interval` for each request.
+    pub jitter: Duration,
+    /// Maximum number of requests in the session. `None` for no limit.
+    pub max_requests: Option<u32>,
+}
+
+impl Default for RateLimit {
+    /// 2 seconds of interval with up to 1 second of jitter, and no request budget.
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::from_secs(2),
+            jitter: Duration::from_secs(1),
+            max_requests: None,
+        }
+    }
+}
+
+impl RateLimit {
+    /// No interval, no jitter and no request budget.
+    pub fn unlimited() -> Self {
+        Self {
+            min_interval: Duration::from_secs(0),
+            jitter: Duration::from_secs(0),
+            max_requests: None,
+        }
+    }
+}
+
+/// Rate limiter shared by requests of a session.
+pub struct RateLimiter {
+    limit: RateLimit,
+    state: Mutex<LimiterState>,
+}
+
+struct LimiterState {
+    /// Time the last allowed request is sent at, possibly in the future.
+    last_request: Option<Instant>,
+    request_count: u32,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            state: Mutex::new(LimiterState {
+                last_request: None,
+                request_count: 0,
+            }),
+        }
+    }
+
+    /// Block the current thread until the next request is allowed,
+    /// and count the request in the budget.
+    /// The time of the request is reserved at once, so that other threads do not wait for
+    /// this thread to wake up but only for their own turns.
+    ///
+    /// # Return value
+    ///
+    /// `Ok` if the request is allowed, or
+    /// `Err` of `ErrorKind::RequestBudgetExceeded` if the budget has run out.
+    pub fn acquire(&self) -> Result<()> {
+        let request_at = {
+            let mut state = self.state.lock().unwrap();
+
+            if let Some(max_requests) = self.limit.max_requests {
+                if state.request_count >= max_requests {
+                    return Err(ErrorKind::RequestBudgetExceeded(max_requests).into());
+                }
+            }
+
+            let now = Instant::now();
+            let request_at = match state.last_request {
+                Some(last_request) => {
+                    let interval = self.limit.min_interval + random_jitter(self.limit.jitter);
+                    cmp::max(now, last_request + interval)
+                }
+                None => now,
+            };
+
+            state.last_request = Some(request_at);
+            state.request_count += 1;
+            request_at
+        };
+
+        let now = Instant::now();
+        if request_at > now {
+            thread::sleep(request_at - now);
+        }
+
+        Ok(())
+    }
+
+    /// Number of requests allowed so far.
+    pub fn request_count(&self) -> u32 {
+        self.state.lock().unwrap().request_count
+    }
+}
+
+fn random_jitter(max: Duration) -> Duration {
+    let max_millis = max.as_secs() * 1000 + u64::from(max.subsec_millis());
+    if max_millis == 0 {
+        return Duration::from_secs(0);
+    }
+
+    Duration::from_millis(rand::thread_rng().gen_range(0, max_millis + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limiter_interval_test() {
+        let limiter = RateLimiter::new(RateLimit {
+            min_interval: Duration::from_millis(50),
+            jitter: Duration::from_millis(10),
+            max_requests: None,
+        });
+
+        let start = Instant::now();
+        limiter.acquire().unwrap();
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        limiter.acquire().unwrap();
+        limiter.acquire().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert_eq!(limiter.request_count(), 3);
+    }
+
+    #[test]
+    fn rate_limiter_unlocked_while_waiting_test() {
+        use std::sync::Arc;
+
+        let limiter = Arc::new(RateLimiter::new(RateLimit {
+            min_interval: Duration::from_millis(200),
+            jitter: Duration::from_secs(0),
+            max_requests: None,
+        }));
+        limiter.acquire().unwrap();
+
+        let waiting = {
+            let limiter = limiter.clone();
+            thread::spawn(move || limiter.acquire().unwrap())
+        };
+        thread::sleep(Duration::from_millis(20));
+
+        // Not blocked by the thread waiting for its turn.
+        let start = Instant::now();
+        assert_eq!(limiter.request_count(), 2);
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        // The third request waits after the second one.
+        limiter.acquire().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        waiting.join().unwrap();
+    }
+
+    #[test]
+    fn rate_limiter_budget_test() {
+        let limiter = RateLimiter::new(RateLimit {
+            max_requests: Some(2),
+            ..RateLimit::unlimited()
+        });
+
+        assert!(limiter.acquire().is_ok());
+        assert!(limiter.acquire().is_ok());
+        assert!(limiter.acquire().is_err());
+        assert_eq!(limiter.request_count(), 2);
+    }
+
+    #[test]
+    fn random_jitter_test() {
+        assert_eq!(random_jitter(Duration::from_secs(0)), Duration::from_secs(0));
+
+        for _ in 0..100 {
+            assert!(random_jitter(Duration::from_millis(20)) <= Duration::from_millis(20));
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Encoding of cluster IDs in 'related:' and 'info:' queries.
+//!
+//! Google Scholar identifies a paper in these queries by its cluster ID encoded in URL-safe
+//! base64; 8 bytes of the ID in little endian, followed by one extra byte.
+
+const TABLE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const EXTRA_BYTE: u8 = 0x09;
+
+/// Length of an encoded cluster ID; 9 bytes in base64.
+const ENCODED_LEN: usize = 12;
+
+/// Encode `cluster_id`, like 11845712235651853713 into "ke2GXYRsZKQJ".
+pub(crate) fn encode(cluster_id: u64) -> String {
+    let mut bytes = Vec::with_capacity(9);
+    for i in 0..8 {
+        bytes.push((cluster_id >> (8 * i)) as u8);
+    }
+    bytes.push(EXTRA_BYTE);
+
+    bytes
+        .chunks(3)
+        .flat_map(|c| {
+            let n = (u32::from(c[0]) << 16) | (u32::from(c[1]) << 8) | u32::from(c[2]);
+            (0..4).rev().map(move |i| TABLE[((n >> (6 * i)) & 0x3f) as usize] as char)
+        })
+        .collect()
+}
+
+/// Decode `encoded` cluster ID. `None` if `encoded` is not an encoded cluster ID.
+pub(crate) fn decode(encoded: &str) -> Option<u64> {
+    if encoded.len() != ENCODED_LEN {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(9);
+    let (mut buf, mut buf_bits) = (0u32, 0);
+    for c in encoded.bytes() {
+        let sextet = TABLE.iter().position(|&t| t == c)? as u32;
+        buf = (buf << 6) | sextet;
+        buf_bits += 6;
+        if buf_bits >= 8 {
+            buf_bits -= 8;
+            bytes.push((buf >> buf_bits) as u8);
+            buf &= (1 << buf_bits) - 1;
+        }
+    }
+
+    let cluster_id = bytes[..8]
+        .iter()
+        .rev()
+        .fold(0u64, |id, &b| (id << 8) | u64::from(b));
+
+    Some(cluster_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_test() {
+        assert_eq!(encode(11845712235651853713), "ke2GXYRsZKQJ");
+        assert_eq!(encode(4401617830405236058), "WmVUkhiwFT0J");
+        assert_eq!(encode(9003587423478211147), "S-KXAA8r83wJ");
+    }
+
+    #[test]
+    fn decode_test() {
+        assert_eq!(decode("n-S3Szmx-uQJ"), Some(16499695044466828447));
+        assert_eq!(decode("KAyT5n6OsHYJ"), Some(8552492368061991976));
+        assert_eq!(decode("n-S3Szmx"), None);
+        assert_eq!(decode("n-S3Szmx+uQJ"), None);
+    }
+
+    #[test]
+    fn decode_encoded_test() {
+        for &cluster_id in &[0, 1, 42, 5545735591029960915, u64::MAX] {
+            assert_eq!(decode(&encode(cluster_id)), Some(cluster_id));
+        }
+    }
+}
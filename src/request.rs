@@ -2,48 +2,536 @@
 
 use std::fmt;
 use std::borrow::Cow;
+use std::sync::Arc;
 
-use reqwest::{self, Url};
+use reqwest::Url;
 
-use super::{GOOGLESCHOLAR_URL_BASE, MAX_RESULT_COUNT};
+use super::{GOOGLESCHOLAR_ROOT_URL, MAX_PUBLICATION_PAGE_SIZE, MAX_RESULT_COUNT};
+use client::{ClientConfig, ScholarClient};
 use errors::*;
+use language::Language;
+use rate_limit::{RateLimit, RateLimiter};
+use related_id;
 
 /// Query to Google Scholar.
 pub trait Query {
-    /// Convert to full URL which could be used to send a request.
-    fn to_url(&self) -> Result<Url>;
+    /// Convert to full URL under `base`, requesting pages in `language`.
+    fn to_localized_url(&self, base: &BaseUrl, language: Language) -> Result<Url>;
+
+    /// Convert to full URL under `base`, requesting pages in English.
+    fn to_url_with_base(&self, base: &BaseUrl) -> Result<Url> {
+        self.to_localized_url(base, Language::default())
+    }
+
+    /// Convert to full URL which could be used to send a request to scholar.google.com.
+    fn to_url(&self) -> Result<Url> {
+        self.to_url_with_base(&BaseUrl::default())
+    }
+}
+
+/// Base URL of Google Scholar, which queries are sent to.
+/// A regional domain or a local server standing in for Google Scholar may be specified.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BaseUrl {
+    root: Url,
+}
+
+impl Default for BaseUrl {
+    /// https://scholar.google.com/
+    fn default() -> Self {
+        Self::new(GOOGLESCHOLAR_ROOT_URL).unwrap()
+    }
+}
+
+impl fmt::Display for BaseUrl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.root)
+    }
+}
+
+impl BaseUrl {
+    /// Create new `BaseUrl` from the root URL of Google Scholar or a server standing in for it,
+    /// such as "https://scholar.google.co.jp/" or "http://localhost:8080/scholar-mock/".
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::{BaseUrl, ClusterQuery, Query};
+    ///
+    /// let base = BaseUrl::new("http://localhost:8080/mock").unwrap();
+    /// assert_eq!(base.scholar_url().as_str(), "http://localhost:8080/mock/scholar");
+    ///
+    /// let url = ClusterQuery::new(42).to_url_with_base(&base).unwrap();
+    /// assert_eq!(url.as_str(), "http://localhost:8080/mock/scholar?cluster=42&hl=en");
+    ///
+    /// assert!(BaseUrl::new("scholar.google.com").is_err());
+    /// ```
+    pub fn new(root: &str) -> Result<Self> {
+        let invalid = || ErrorKind::InvalidBaseUrl(root.to_owned());
+
+        let mut root = Url::parse(root).map_err(|_| invalid())?;
+        if root.cannot_be_a_base() || !(root.scheme() == "http" || root.scheme() == "https") {
+            return Err(invalid().into());
+        }
+
+        // Paths are joined to the root as a directory.
+        if !root.path().ends_with('/') {
+            let path = format!("{}/", root.path());
+            root.set_path(&path);
+        }
+        root.set_query(None);
+        root.set_fragment(None);
+
+        Ok(Self { root })
+    }
+
+    /// Google Scholar of a regional domain, such as "co.jp" for scholar.google.co.jp.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::BaseUrl;
+    ///
+    /// let base = BaseUrl::regional("co.jp").unwrap();
+    /// assert_eq!(base.to_string(), "https://scholar.google.co.jp/");
+    /// ```
+    pub fn regional(domain: &str) -> Result<Self> {
+        Self::new(&format!("https://scholar.google.{}/", domain))
+    }
+
+    /// URL of searching papers, like "https://scholar.google.com/scholar".
+    pub fn scholar_url(&self) -> Url {
+        self.root.join("scholar").unwrap()
+    }
+
+    /// URL of author profiles, like "https://scholar.google.com/citations".
+    pub fn citations_url(&self) -> Url {
+        self.root.join("citations").unwrap()
+    }
+
+    /// Move `url` of any Google Scholar domain under this base URL, keeping its path and query.
+    /// URLs of other hosts are returned as they are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate reqwest;
+    /// extern crate scholar;
+    ///
+    /// use reqwest::Url;
+    /// use scholar::request::BaseUrl;
+    ///
+    /// # fn main() {
+    /// let base = BaseUrl::new("http://localhost:8080/").unwrap();
+    ///
+    /// let url = Url::parse("https://scholar.google.co.jp/scholar?cites=42&hl=en").unwrap();
+    /// assert_eq!(base.rebase(&url).as_str(), "http://localhost:8080/scholar?cites=42&hl=en");
+    ///
+    /// let url = Url::parse("https://example.com/paper.pdf").unwrap();
+    /// assert_eq!(base.rebase(&url), url);
+    /// # }
+    /// ```
+    pub fn rebase(&self, url: &Url) -> Url {
+        let is_scholar = url.host_str()
+            .map(|host| host.starts_with("scholar.google."))
+            .unwrap_or(false);
+        if !is_scholar {
+            return url.clone();
+        }
+
+        let mut path = url.path().trim_start_matches('/').to_owned();
+        if let Some(query) = url.query() {
+            path.push('?');
+            path.push_str(query);
+        }
+        self.root.join(&path).unwrap_or_else(|_| url.clone())
+    }
+
+    /// Same as `rebase`, for a URL in `str`. Unparsable URLs are returned as they are.
+    pub fn rebase_str(&self, url: &str) -> String {
+        match Url::parse(url) {
+            Ok(url) => self.rebase(&url).into_string(),
+            Err(_) => url.to_owned(),
+        }
+    }
+}
+
+/// Query whose results are split into pages.
+///
+/// Google Scholar returns at most `MAX_RESULT_COUNT` results per page.
+/// Later pages are requested by specifying the offset of the first result.
+pub trait PagedQuery: Query {
+    /// Set `start` to the offset of the first result in the page.
+    fn set_start(&mut self, start: u32);
+
+    fn get_start(&self) -> u32;
+
+    /// Set `max_result_count` to maximum number of results in the page.
+    /// The `max_result_count` will be rounded down to `MAX_RESULT_COUNT`.
+    fn set_count(&mut self, max_result_count: u32);
+
+    fn get_count(&self) -> u32;
+}
+
+// Implement `PagedQuery` by the inherent methods of the same names.
+macro_rules! impl_paged_query {
+    ($struct: ident) => {
+        impl PagedQuery for $struct {
+            fn set_start(&mut self, start: u32) {
+                self.set_start(start);
+            }
+
+            fn get_start(&self) -> u32 {
+                self.get_start()
+            }
+
+            fn set_count(&mut self, max_result_count: u32) {
+                self.set_count(max_result_count);
+            }
+
+            fn get_count(&self) -> u32 {
+                self.get_count()
+            }
+        }
+    }
 }
 
-/// Sends a GET request with `query` to Google Scholar.
+/// Sends a GET request with `query` to Google Scholar, with a new `ScholarClient`,
+/// requesting the page in `language`.
+/// Use `ScholarClient` directly to send successive requests, reusing connections and cookies.
+///
+/// Requests sent by this function are paced by a rate limiter with the default `RateLimit`,
+/// shared in the process.
 ///
 /// # Return value
 ///
 /// `Ok` of response body in `String`, or `Error`.
-pub fn send_request<Q: Query + fmt::Display>(query: &Q, verbose: bool) -> Result<String> {
-    use reqwest::header::UserAgent;
+pub fn send_request<Q>(query: &Q, language: Language, verbose: bool) -> Result<String>
+where
+    Q: Query + fmt::Display,
+{
+    lazy_static! {
+        static ref RATE_LIMITER: Arc<RateLimiter> = Arc::new(RateLimiter::new(RateLimit::default()));
+    }
+
+    let config = ClientConfig {
+        language,
+        verbose,
+        ..ClientConfig::default()
+    };
+    let client = ScholarClient::with_rate_limiter(config, Arc::clone(&RATE_LIMITER))?;
+    client.send(query)
+}
+
+/// Order of results of `SearchQuery` and `CitationQuery`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Relevance,
+    /// Newest first, by the date Google Scholar added the papers.
+    /// Google Scholar lists only papers added recently in this order,
+    /// showing how many days ago each one was added.
+    Date,
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SortOrder::Relevance => write!(f, "relevance"),
+            SortOrder::Date => write!(f, "date"),
+        }
+    }
+}
+
+// `scisbd=1` sorts by date; relevance is the default order of Google Scholar.
+fn append_sort_order(query: String, sort_order: SortOrder) -> String {
+    match sort_order {
+        SortOrder::Relevance => query,
+        SortOrder::Date => format!("{}&scisbd=1", query),
+    }
+}
+
+/// Documents searched by `SearchQuery`: articles or case law.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Collection {
+    /// Articles, including patents if `include_patents` is `true`.
+    Articles { include_patents: bool },
+    /// Case law of US courts.
+    CaseLaw(Jurisdiction),
+}
+
+impl Default for Collection {
+    /// Articles including patents, as Google Scholar searches by default.
+    fn default() -> Self {
+        Collection::Articles {
+            include_patents: true,
+        }
+    }
+}
+
+impl fmt::Display for Collection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Collection::Articles {
+                include_patents: true,
+            } => write!(f, "articles including patents"),
+            Collection::Articles {
+                include_patents: false,
+            } => write!(f, "articles excluding patents"),
+            Collection::CaseLaw(ref jurisdiction) => write!(f, "case law of {}", jurisdiction),
+        }
+    }
+}
+
+impl Collection {
+    // Value of `as_sdt` parameter, with commas escaped.
+    fn as_sdt(&self) -> String {
+        match *self {
+            // "5" is the default location of Google Scholar.
+            Collection::Articles {
+                include_patents: true,
+            } => String::from("0%2C5"),
+            Collection::Articles {
+                include_patents: false,
+            } => String::from("1%2C5"),
+            // "2006" is sent by the "Case law" link of Google Scholar.
+            Collection::CaseLaw(Jurisdiction::AllCourts) => String::from("2006"),
+            Collection::CaseLaw(Jurisdiction::Courts(ref court_ids)) => {
+                let mut sdt = String::from("4");
+                for id in court_ids {
+                    sdt.push_str(&format!("%2C{}", id));
+                }
+                sdt
+            }
+        }
+    }
+}
+
+/// Courts whose case law is searched.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Jurisdiction {
+    /// All federal and state courts.
+    AllCourts,
+    /// Courts of these IDs, as selected in "Select courts" of Google Scholar.
+    Courts(Vec<u32>),
+}
+
+impl fmt::Display for Jurisdiction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Jurisdiction::AllCourts => write!(f, "all courts"),
+            Jurisdiction::Courts(ref court_ids) => {
+                let ids = court_ids
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+                write!(f, "courts {}", ids.join(", "))
+            }
+        }
+    }
+}
+
+/// Words, phrases and authors to search for, shared by `SearchQuery` and `CitationQuery`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchTerms {
+    words: Option<String>,
+    authors: Option<String>,
+}
+
+impl SearchTerms {
+    /// Set `words` to search for.
+    /// 'Words' or 'phrase' terms specified so far will be cleared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::SearchTerms;
+    ///
+    /// let mut terms = SearchTerms::default();
+    ///
+    /// terms.set_words("foo");
+    /// assert_eq!(terms.get_words(), &Some(String::from("foo")));
+    ///
+    /// terms.set_words("bar");
+    /// assert_eq!(terms.get_words(), &Some(String::from("bar")));
+    /// ```
+    pub fn set_words(&mut self, words: &str) {
+        self.words = Some(words.to_owned());
+    }
+
+    /// Append `words` to search for.
+    /// If some words or phrases are specified already,
+    /// `words` will be appended to the terms with one space.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::SearchTerms;
+    ///
+    /// let mut terms = SearchTerms::default();
+    /// assert!(terms.get_words().is_none());
+    ///
+    /// terms.append_words("foo");
+    /// assert_eq!(terms.get_words(), &Some(String::from("foo")));
+    ///
+    /// terms.append_words("bar");
+    /// assert_eq!(terms.get_words(), &Some(String::from("foo bar")));
+    /// ```
+    pub fn append_words(&mut self, words: &str) {
+        append_with_space(&mut self.words, words);
+    }
+
+    pub fn get_words(&self) -> &Option<String> {
+        &self.words
+    }
+
+    /// Set `phrase` to search for.
+    /// 'Words' or 'phrase' terms specified so far will be cleared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::SearchTerms;
+    ///
+    /// let mut terms = SearchTerms::default();
+    ///
+    /// terms.set_phrase("foo bar");
+    /// assert_eq!(terms.get_words(), &Some(String::from(r#""foo bar""#)));
+    /// ```
+    pub fn set_phrase(&mut self, phrase: &str) {
+        self.set_words(&format!("\"{}\"", phrase));
+    }
+
+    /// Append `phrase` to search for.
+    /// If some words or phrases are set already,
+    /// `phrase` will be appended to the terms with one space.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::SearchTerms;
+    ///
+    /// let mut terms = SearchTerms::default();
+    ///
+    /// terms.append_phrase("foo bar");
+    /// assert_eq!(terms.get_words(), &Some(String::from(r#""foo bar""#)));
+    ///
+    /// terms.append_phrase("baz qux");
+    /// assert_eq!(terms.get_words(), &Some(String::from(r#""foo bar" "baz qux""#)));
+    /// ```
+    pub fn append_phrase(&mut self, phrase: &str) {
+        self.append_words(&format!("\"{}\"", phrase));
+    }
 
-    const USER_AGENT: &str = "Mozilla/5.0 (X11; Linux x86_64; rv:57.0) Gecko/20100101 Firefox/57.0";
+    /// Set `authors` to search for.
+    /// 'Authors' terms specified so far will be cleared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::SearchTerms;
+    ///
+    /// let mut terms = SearchTerms::default();
+    ///
+    /// terms.set_authors("albert");
+    /// assert_eq!(terms.get_authors(), &Some(String::from("albert")));
+    ///
+    /// terms.set_authors("einstein");
+    /// assert_eq!(terms.get_authors(), &Some(String::from("einstein")));
+    /// ```
+    pub fn set_authors(&mut self, authors: &str) {
+        self.authors = Some(authors.to_owned());
+    }
 
-    let client = reqwest::Client::new();
-    let url = query.to_url()?;
+    /// Append `authors` to search for.
+    /// If some authors are set already,
+    /// `authors` will be appended to the terms with one space.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::SearchTerms;
+    ///
+    /// let mut terms = SearchTerms::default();
+    ///
+    /// terms.append_authors("albert");
+    /// assert_eq!(terms.get_authors(), &Some(String::from("albert")));
+    ///
+    /// terms.append_authors("einstein");
+    /// assert_eq!(terms.get_authors(), &Some(String::from("albert einstein")));
+    /// ```
+    pub fn append_authors(&mut self, authors: &str) {
+        append_with_space(&mut self.authors, authors);
+    }
 
-    if verbose {
-        println!("Sending {}", query);
-        println!("(URL: {})", url);
+    pub fn get_authors(&self) -> &Option<String> {
+        &self.authors
     }
+}
+
+// Implement the setters and getters of `SearchTerms` on `$struct` with `terms` field.
+macro_rules! impl_search_terms {
+    ($struct: ident) => {
+        impl $struct {
+            /// Set `words` to search for. See `SearchTerms::set_words`.
+            pub fn set_words(&mut self, words: &str) {
+                self.terms.set_words(words);
+            }
+
+            /// Append `words` to search for. See `SearchTerms::append_words`.
+            pub fn append_words(&mut self, words: &str) {
+                self.terms.append_words(words);
+            }
+
+            pub fn get_words(&self) -> &Option<String> {
+                self.terms.get_words()
+            }
+
+            /// Set `phrase` to search for. See `SearchTerms::set_phrase`.
+            pub fn set_phrase(&mut self, phrase: &str) {
+                self.terms.set_phrase(phrase);
+            }
+
+            /// Append `phrase` to search for. See `SearchTerms::append_phrase`.
+            pub fn append_phrase(&mut self, phrase: &str) {
+                self.terms.append_phrase(phrase);
+            }
+
+            /// Set `authors` to search for. See `SearchTerms::set_authors`.
+            pub fn set_authors(&mut self, authors: &str) {
+                self.terms.set_authors(authors);
+            }
+
+            /// Append `authors` to search for. See `SearchTerms::append_authors`.
+            pub fn append_authors(&mut self, authors: &str) {
+                self.terms.append_authors(authors);
+            }
 
-    let mut res = client.get(url).header(UserAgent::new(USER_AGENT)).send()?;
+            pub fn get_authors(&self) -> &Option<String> {
+                self.terms.get_authors()
+            }
 
-    let body = res.text()?;
-    Ok(body)
+            pub fn get_terms(&self) -> &SearchTerms {
+                &self.terms
+            }
+        }
+    }
 }
 
 /// Query to search Google Scholar for papers.
+#[derive(Clone)]
 pub struct SearchQuery {
     max_result_count: u32,
-    words: Option<String>,
-    authors: Option<String>,
+    start: u32,
+    terms: SearchTerms,
+    excluded_words: Option<String>,
+    publication: Option<String>,
     title_only: bool,
+    year_from: Option<u32>,
+    year_to: Option<u32>,
+    sort_order: SortOrder,
+    collection: Collection,
+    include_citations: bool,
 }
 
 impl fmt::Display for SearchQuery {
@@ -53,12 +541,28 @@ impl fmt::Display for SearchQuery {
             r#"query to search for papers of:
           authors: {},
             words: {},
+   excluded words: {},
+      publication: {},
 title-only search: {},
-     max #results: {}"#,
-            option_unspecified(&self.authors),
-            option_unspecified(&self.words),
+        year from: {},
+          year to: {},
+       collection: {},
+include citations: {},
+       sort order: {},
+     max #results: {},
+           offset: {}"#,
+            option_unspecified(&self.terms.authors),
+            option_unspecified(&self.terms.words),
+            option_unspecified(&self.excluded_words),
+            option_unspecified(&self.publication),
             self.title_only,
-            self.max_result_count
+            option_unspecified(&self.year_from),
+            option_unspecified(&self.year_to),
+            self.collection,
+            self.include_citations,
+            self.sort_order,
+            self.max_result_count,
+            self.start
         )
     }
 }
@@ -79,15 +583,22 @@ impl Default for SearchQuery {
     fn default() -> Self {
         SearchQuery {
             max_result_count: DEFAULT_MAX_RESULT_COUNT,
-            words: None,
-            authors: None,
+            start: 0,
+            terms: SearchTerms::default(),
+            excluded_words: None,
+            publication: None,
             title_only: false,
+            year_from: None,
+            year_to: None,
+            sort_order: SortOrder::default(),
+            collection: Collection::default(),
+            include_citations: true,
         }
     }
 }
 
 impl Query for SearchQuery {
-    fn to_url(&self) -> Result<Url> {
+    fn to_localized_url(&self, base: &BaseUrl, language: Language) -> Result<Url> {
         if !self.is_valid() {
             return Err(ErrorKind::InvalidQuery.into());
         }
@@ -101,27 +612,45 @@ impl Query for SearchQuery {
             }
         }
 
-        let mut url = Url::parse(GOOGLESCHOLAR_URL_BASE).unwrap();
+        macro_rules! option_year {
+            ($x: expr) => {
+                match $x {
+                    Some(y) => y.to_string(),
+                    None => String::new(),
+                }
+            }
+        }
+
+        let mut url = base.scholar_url();
 
         let query = format!(
             "as_q={}\
              &as_epq=\
-             &as_eq=\
+             &as_eq={}\
              &as_occt={}\
              &as_sauthors={}\
-             &as_publication=\
-             &as_ylo=\
-             &as_yhi=\
-             &as_vis=0\
+             &as_publication={}\
+             &as_ylo={}\
+             &as_yhi={}\
+             &as_vis={}\
              &btnG=\
-             &hl=en\
+             &hl={}\
              &num={}\
-             &as_sdt=0%2C5",
-            option_stringify!(self.words),
+             &as_sdt={}",
+            option_stringify!(self.terms.words),
+            option_stringify!(self.excluded_words),
             if self.title_only { "title" } else { "any" },
-            option_stringify!(self.authors),
+            option_stringify!(self.terms.authors),
+            option_stringify!(self.publication),
+            option_year!(self.year_from),
+            option_year!(self.year_to),
+            if self.include_citations { 0 } else { 1 },
+            language.code(),
             self.max_result_count,
+            self.collection.as_sdt(),
         );
+        let query = append_sort_order(query, self.sort_order);
+        let query = append_start(query, self.start);
         url.set_query(Some(&query));
 
         Ok(url)
@@ -153,8 +682,8 @@ impl SearchQuery {
         self.max_result_count
     }
 
-    /// Set `words` to search query.
-    /// 'Words' or 'phrase' query specified so far will be cleared.
+    /// Set `start` to the offset of the first search result.
+    /// Search results from `start`-th (0-based) one will be listed.
     ///
     /// # Example
     ///
@@ -162,20 +691,22 @@ impl SearchQuery {
     /// use scholar::request::SearchQuery;
     ///
     /// let mut q = SearchQuery::default();
+    /// assert_eq!(q.get_start(), 0);
     ///
-    /// q.set_words("foo");
-    /// assert_eq!(q.get_words(), &Some(String::from("foo")));
-    ///
-    /// q.set_words("bar");
-    /// assert_eq!(q.get_words(), &Some(String::from("bar")));
+    /// q.set_start(10);
+    /// assert_eq!(q.get_start(), 10);
     /// ```
-    pub fn set_words(&mut self, words: &str) {
-        self.words = Some(words.to_owned());
+    pub fn set_start(&mut self, start: u32) {
+        self.start = start;
+    }
+
+    pub fn get_start(&self) -> u32 {
+        self.start
     }
 
-    /// Append `words` to search query.
-    /// If some 'words' or 'phrases' query is specified already,
-    /// `words` will be appended to the query with one space.
+    /// Set `excluded_words` to search query.
+    /// Papers containing any of these words will be excluded.
+    /// 'Excluded words' query specified so far will be cleared.
     ///
     /// # Example
     ///
@@ -183,32 +714,20 @@ impl SearchQuery {
     /// use scholar::request::SearchQuery;
     ///
     /// let mut q = SearchQuery::default();
-    /// assert!(q.get_words().is_none());
     ///
-    /// q.append_words("foo");
-    /// assert_eq!(q.get_words(), &Some(String::from("foo")));
+    /// q.set_excluded_words("foo");
+    /// assert_eq!(q.get_excluded_words(), &Some(String::from("foo")));
     ///
-    /// q.append_words("bar");
-    /// assert_eq!(q.get_words(), &Some(String::from("foo bar")));
+    /// q.set_excluded_words("bar");
+    /// assert_eq!(q.get_excluded_words(), &Some(String::from("bar")));
     /// ```
-    pub fn append_words(&mut self, words: &str) {
-        match self.words {
-            Some(ref mut w) => {
-                w.push(' ');
-                w.push_str(words);
-            }
-            None => {
-                self.words = Some(words.to_owned());
-            }
-        }
-    }
-
-    pub fn get_words(&self) -> &Option<String> {
-        &self.words
+    pub fn set_excluded_words(&mut self, excluded_words: &str) {
+        self.excluded_words = Some(excluded_words.to_owned());
     }
 
-    /// Set `phrase` to search query.
-    /// 'Words' or 'phrase' query specified so far will be cleared.
+    /// Append `excluded_words` to search query.
+    /// If some 'excluded words' query is set already,
+    /// `excluded_words` will be appended to the query with one space.
     ///
     /// # Example
     ///
@@ -217,16 +736,23 @@ impl SearchQuery {
     ///
     /// let mut q = SearchQuery::default();
     ///
-    /// q.set_phrase("foo bar");
-    /// assert_eq!(q.get_words(), &Some(String::from(r#""foo bar""#)));
+    /// q.append_excluded_words("foo");
+    /// assert_eq!(q.get_excluded_words(), &Some(String::from("foo")));
+    ///
+    /// q.append_excluded_words("bar");
+    /// assert_eq!(q.get_excluded_words(), &Some(String::from("foo bar")));
     /// ```
-    pub fn set_phrase(&mut self, phrase: &str) {
-        self.set_words(&format!("\"{}\"", phrase));
+    pub fn append_excluded_words(&mut self, excluded_words: &str) {
+        append_with_space(&mut self.excluded_words, excluded_words);
     }
 
-    /// Append `phrase` to search query.
-    /// If some 'words' or 'phrases' query is set already,
-    /// `phrase` will be appended to the query with one space.
+    pub fn get_excluded_words(&self) -> &Option<String> {
+        &self.excluded_words
+    }
+
+    /// Set `publication` to search query.
+    /// Only papers published in this journal, conference, etc. will be searched.
+    /// 'Publication' query specified so far will be cleared.
     ///
     /// # Example
     ///
@@ -235,18 +761,19 @@ impl SearchQuery {
     ///
     /// let mut q = SearchQuery::default();
     ///
-    /// q.append_phrase("foo bar");
-    /// assert_eq!(q.get_words(), &Some(String::from(r#""foo bar""#)));
+    /// q.set_publication("nature");
+    /// assert_eq!(q.get_publication(), &Some(String::from("nature")));
     ///
-    /// q.append_phrase("baz qux");
-    /// assert_eq!(q.get_words(), &Some(String::from(r#""foo bar" "baz qux""#)));
+    /// q.set_publication("science");
+    /// assert_eq!(q.get_publication(), &Some(String::from("science")));
     /// ```
-    pub fn append_phrase(&mut self, phrase: &str) {
-        self.append_words(&format!("\"{}\"", phrase));
+    pub fn set_publication(&mut self, publication: &str) {
+        self.publication = Some(publication.to_owned());
     }
 
-    /// Set `authors` to search query.
-    /// 'Authors' query specified so far will be cleared.
+    /// Append `publication` to search query.
+    /// If some 'publication' query is set already,
+    /// `publication` will be appended to the query with one space.
     ///
     /// # Example
     ///
@@ -255,19 +782,24 @@ impl SearchQuery {
     ///
     /// let mut q = SearchQuery::default();
     ///
-    /// q.set_authors("albert");
-    /// assert_eq!(q.get_authors(), &Some(String::from("albert")));
+    /// q.append_publication("physical");
+    /// assert_eq!(q.get_publication(), &Some(String::from("physical")));
     ///
-    /// q.set_authors("einstein");
-    /// assert_eq!(q.get_authors(), &Some(String::from("einstein")));
+    /// q.append_publication("review");
+    /// assert_eq!(q.get_publication(), &Some(String::from("physical review")));
     /// ```
-    pub fn set_authors(&mut self, authors: &str) {
-        self.authors = Some(authors.to_owned());
+    pub fn append_publication(&mut self, publication: &str) {
+        append_with_space(&mut self.publication, publication);
+    }
+
+    pub fn get_publication(&self) -> &Option<String> {
+        &self.publication
     }
 
-    /// Append `authors` to search query.
-    /// If some 'authors' query is set already,
-    /// `authors` will be appended to the query with one space.
+    /// Enable or disable title-only search.
+    ///
+    /// To enable, set `title_only` argument `true`;
+    /// To disable, set `title_only` argument `false`.
     ///
     /// # Example
     ///
@@ -276,32 +808,46 @@ impl SearchQuery {
     ///
     /// let mut q = SearchQuery::default();
     ///
-    /// q.append_authors("albert");
-    /// assert_eq!(q.get_authors(), &Some(String::from("albert")));
+    /// assert_eq!(q.get_title_only(), false);
     ///
-    /// q.append_authors("einstein");
-    /// assert_eq!(q.get_authors(), &Some(String::from("albert einstein")));
-    /// ```
-    pub fn append_authors(&mut self, authors: &str) {
-        match self.authors {
-            Some(ref mut a) => {
-                a.push(' ');
-                a.push_str(authors);
-            }
-            None => {
-                self.authors = Some(authors.to_owned());
-            }
-        }
+    /// q.set_title_only(true);
+    /// assert_eq!(q.get_title_only(), true);
+    ///
+    /// q.set_title_only(false);
+    /// assert_eq!(q.get_title_only(), false);
+    /// ```
+    pub fn set_title_only(&mut self, title_only: bool) {
+        self.title_only = title_only;
     }
 
-    pub fn get_authors(&self) -> &Option<String> {
-        &self.authors
+    pub fn get_title_only(&self) -> bool {
+        self.title_only
     }
 
-    /// Enable or disable title-only search.
+    /// Set the lower bound of published year.
+    /// Papers published in or after `year_from` will be searched.
     ///
-    /// To enable, set `title_only` argument `true`;
-    /// To disable, set `title_only` argument `false`.
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::SearchQuery;
+    ///
+    /// let mut q = SearchQuery::default();
+    /// assert!(q.get_year_from().is_none());
+    ///
+    /// q.set_year_from(2015);
+    /// assert_eq!(q.get_year_from(), Some(2015));
+    /// ```
+    pub fn set_year_from(&mut self, year_from: u32) {
+        self.year_from = Some(year_from);
+    }
+
+    pub fn get_year_from(&self) -> Option<u32> {
+        self.year_from
+    }
+
+    /// Set the upper bound of published year.
+    /// Papers published in or before `year_to` will be searched.
     ///
     /// # Example
     ///
@@ -309,32 +855,137 @@ impl SearchQuery {
     /// use scholar::request::SearchQuery;
     ///
     /// let mut q = SearchQuery::default();
+    /// assert!(q.get_year_to().is_none());
     ///
-    /// assert_eq!(q.get_title_only(), false);
+    /// q.set_year_to(2018);
+    /// assert_eq!(q.get_year_to(), Some(2018));
+    /// ```
+    pub fn set_year_to(&mut self, year_to: u32) {
+        self.year_to = Some(year_to);
+    }
+
+    pub fn get_year_to(&self) -> Option<u32> {
+        self.year_to
+    }
+
+    /// Set documents to search, articles or case law.
     ///
-    /// q.set_title_only(true);
-    /// assert_eq!(q.get_title_only(), true);
+    /// # Example
     ///
-    /// q.set_title_only(false);
-    /// assert_eq!(q.get_title_only(), false);
     /// ```
-    pub fn set_title_only(&mut self, title_only: bool) {
-        self.title_only = title_only;
+    /// use scholar::request::{Collection, Jurisdiction, SearchQuery};
+    ///
+    /// let mut q = SearchQuery::default();
+    /// assert_eq!(
+    ///     q.get_collection(),
+    ///     &Collection::Articles {
+    ///         include_patents: true
+    ///     }
+    /// );
+    ///
+    /// q.set_collection(Collection::CaseLaw(Jurisdiction::AllCourts));
+    /// assert_eq!(
+    ///     q.get_collection(),
+    ///     &Collection::CaseLaw(Jurisdiction::AllCourts)
+    /// );
+    /// ```
+    pub fn set_collection(&mut self, collection: Collection) {
+        self.collection = collection;
     }
 
-    pub fn get_title_only(&self) -> bool {
-        self.title_only
+    pub fn get_collection(&self) -> &Collection {
+        &self.collection
+    }
+
+    /// Include or exclude `[CITATION]` entries, which Google Scholar knows only from citations.
+    /// Included by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::SearchQuery;
+    ///
+    /// let mut q = SearchQuery::default();
+    /// assert_eq!(q.get_include_citations(), true);
+    ///
+    /// q.set_include_citations(false);
+    /// assert_eq!(q.get_include_citations(), false);
+    /// ```
+    pub fn set_include_citations(&mut self, include_citations: bool) {
+        self.include_citations = include_citations;
+    }
+
+    pub fn get_include_citations(&self) -> bool {
+        self.include_citations
+    }
+
+    /// Set the order of search results.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::{SearchQuery, SortOrder};
+    ///
+    /// let mut q = SearchQuery::default();
+    /// assert_eq!(q.get_sort_order(), SortOrder::Relevance);
+    ///
+    /// q.set_sort_order(SortOrder::Date);
+    /// assert_eq!(q.get_sort_order(), SortOrder::Date);
+    /// ```
+    pub fn set_sort_order(&mut self, sort_order: SortOrder) {
+        self.sort_order = sort_order;
+    }
+
+    pub fn get_sort_order(&self) -> SortOrder {
+        self.sort_order
     }
 
     fn is_valid(&self) -> bool {
-        self.words.is_some() || self.authors.is_some()
+        if let (Some(from), Some(to)) = (self.year_from, self.year_to) {
+            if from > to {
+                return false;
+            }
+        }
+
+        self.terms.words.is_some() || self.terms.authors.is_some() || self.publication.is_some()
+    }
+}
+
+impl_search_terms!(SearchQuery);
+impl_paged_query!(SearchQuery);
+
+fn append_with_space(target: &mut Option<String>, s: &str) {
+    match *target {
+        Some(ref mut t) => {
+            t.push(' ');
+            t.push_str(s);
+        }
+        None => {
+            *target = Some(s.to_owned());
+        }
+    }
+}
+
+// `start` is omitted for the first page, as Google Scholar does.
+fn append_start(query: String, start: u32) -> String {
+    if start > 0 {
+        format!("{}&start={}", query, start)
+    } else {
+        query
     }
 }
 
 /// Query to get list of papers which cites a paper.
+/// Citers may be filtered by words and authors, as "Search within citing articles" does.
+#[derive(Clone)]
 pub struct CitationQuery {
     citation_url: String,
     max_result_count: u32,
+    start: u32,
+    sort_order: SortOrder,
+    terms: SearchTerms,
+    year_from: Option<u32>,
+    year_to: Option<u32>,
 }
 
 impl fmt::Display for CitationQuery {
@@ -343,18 +994,64 @@ impl fmt::Display for CitationQuery {
             f,
             r#"query to get list of papers which cites a paper of:
 URL of the paper: {},
-    max #results: {}"#,
-            self.citation_url, self.max_result_count
+           words: {},
+         authors: {},
+       year from: {},
+         year to: {},
+      sort order: {},
+    max #results: {},
+          offset: {}"#,
+            self.citation_url,
+            option_unspecified(&self.terms.words),
+            option_unspecified(&self.terms.authors),
+            option_unspecified(&self.year_from),
+            option_unspecified(&self.year_to),
+            self.sort_order,
+            self.max_result_count,
+            self.start
         )
     }
 }
 
 impl Query for CitationQuery {
-    fn to_url(&self) -> Result<Url> {
-        let mut url = Url::parse(&self.citation_url).unwrap();
+    fn to_localized_url(&self, base: &BaseUrl, language: Language) -> Result<Url> {
+        // Parameters set by this query replace the same ones in `citation_url`.
+        const OWN_PARAMS: &[&str] = &[
+            "q", "scipsc", "as_ylo", "as_yhi", "hl", "num", "scisbd", "start"
+        ];
+
+        if let (Some(from), Some(to)) = (self.year_from, self.year_to) {
+            if from > to {
+                return Err(ErrorKind::InvalidQuery.into());
+            }
+        }
+
+        let mut url = base.rebase(&Url::parse(&self.citation_url).unwrap());
         let query = {
-            let q = url.query().unwrap();
-            format!("{}&hl=en&num={}", q, self.max_result_count)
+            let mut params = url.query()
+                .unwrap_or("")
+                .split('&')
+                .filter(|param| {
+                    let name = param.split('=').next().unwrap();
+                    !name.is_empty() && !OWN_PARAMS.contains(&name)
+                })
+                .map(ToOwned::to_owned)
+                .collect::<Vec<_>>();
+            if let Some(keywords) = self.keywords() {
+                params.push(format!("q={}", keywords));
+                params.push(String::from("scipsc=1"));
+            }
+            if let Some(year_from) = self.year_from {
+                params.push(format!("as_ylo={}", year_from));
+            }
+            if let Some(year_to) = self.year_to {
+                params.push(format!("as_yhi={}", year_to));
+            }
+            params.push(format!("hl={}", language.code()));
+            params.push(format!("num={}", self.max_result_count));
+
+            let q = append_sort_order(params.join("&"), self.sort_order);
+            append_start(q, self.start)
         };
         url.set_query(Some(&query));
 
@@ -369,9 +1066,35 @@ impl CitationQuery {
         Self {
             citation_url: citation_url.to_owned(),
             max_result_count: DEFAULT_MAX_RESULT_COUNT,
+            start: 0,
+            sort_order: SortOrder::default(),
+            terms: SearchTerms::default(),
+            year_from: None,
+            year_to: None,
         }
     }
 
+    /// Create new CitationQuery listing papers which cite a paper of `cluster_id`,
+    /// with `citation_url` under `base`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::{BaseUrl, CitationQuery, Query};
+    ///
+    /// let base = BaseUrl::regional("co.jp").unwrap();
+    /// let q = CitationQuery::from_cluster_id(42, &base);
+    /// assert_eq!(
+    ///     q.to_url_with_base(&base).unwrap().as_str(),
+    ///     "https://scholar.google.co.jp/scholar?cites=42&hl=en&num=5"
+    /// );
+    /// ```
+    pub fn from_cluster_id(cluster_id: u64, base: &BaseUrl) -> Self {
+        let mut url = base.scholar_url();
+        url.set_query(Some(&format!("cites={}", cluster_id)));
+        Self::new(url.as_str())
+    }
+
     /// Set `max_result_count` to maximum number of search result.
     /// The `max_result_count` will be rounded down to 10.
     ///
@@ -395,8 +1118,114 @@ impl CitationQuery {
     pub fn get_count(&self) -> u32 {
         self.max_result_count
     }
+
+    /// Set `start` to the offset of the first citer.
+    /// Citers from `start`-th (0-based) one will be listed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::CitationQuery;
+    ///
+    /// let mut q = CitationQuery::new("https://example.com");
+    /// assert_eq!(q.get_start(), 0);
+    ///
+    /// q.set_start(10);
+    /// assert_eq!(q.get_start(), 10);
+    /// ```
+    pub fn set_start(&mut self, start: u32) {
+        self.start = start;
+    }
+
+    pub fn get_start(&self) -> u32 {
+        self.start
+    }
+
+    // Words and authors in one `q` parameter, as "Search within citing articles" sends:
+    // "foo bar author:\"albert einstein\"", quoting the name of more than one word.
+    fn keywords(&self) -> Option<String> {
+        let mut keywords = self.terms.words.clone();
+        if let Some(ref authors) = self.terms.authors {
+            let name = authors.split_whitespace().collect::<Vec<_>>().join(" ");
+            if name.contains(' ') {
+                append_with_space(&mut keywords, &format!("author:\"{}\"", name));
+            } else if !name.is_empty() {
+                append_with_space(&mut keywords, &format!("author:{}", name));
+            }
+        }
+        keywords
+    }
+
+    /// Set the lower bound of published year.
+    /// Citers published in or after `year_from` will be listed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::CitationQuery;
+    ///
+    /// let mut q = CitationQuery::new("https://example.com");
+    /// assert!(q.get_year_from().is_none());
+    ///
+    /// q.set_year_from(2015);
+    /// assert_eq!(q.get_year_from(), Some(2015));
+    /// ```
+    pub fn set_year_from(&mut self, year_from: u32) {
+        self.year_from = Some(year_from);
+    }
+
+    pub fn get_year_from(&self) -> Option<u32> {
+        self.year_from
+    }
+
+    /// Set the upper bound of published year.
+    /// Citers published in or before `year_to` will be listed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::CitationQuery;
+    ///
+    /// let mut q = CitationQuery::new("https://example.com");
+    /// assert!(q.get_year_to().is_none());
+    ///
+    /// q.set_year_to(2018);
+    /// assert_eq!(q.get_year_to(), Some(2018));
+    /// ```
+    pub fn set_year_to(&mut self, year_to: u32) {
+        self.year_to = Some(year_to);
+    }
+
+    pub fn get_year_to(&self) -> Option<u32> {
+        self.year_to
+    }
+
+    /// Set the order of citers.
+    /// Sorting by date lists the newest citers first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::{CitationQuery, SortOrder};
+    ///
+    /// let mut q = CitationQuery::new("https://example.com");
+    /// assert_eq!(q.get_sort_order(), SortOrder::Relevance);
+    ///
+    /// q.set_sort_order(SortOrder::Date);
+    /// assert_eq!(q.get_sort_order(), SortOrder::Date);
+    /// ```
+    pub fn set_sort_order(&mut self, sort_order: SortOrder) {
+        self.sort_order = sort_order;
+    }
+
+    pub fn get_sort_order(&self) -> SortOrder {
+        self.sort_order
+    }
 }
 
+impl_search_terms!(CitationQuery);
+impl_paged_query!(CitationQuery);
+
 /// Query to get paper cluster of a specified cluster ID.
 pub struct ClusterQuery {
     cluster_id: u64,
@@ -413,9 +1242,9 @@ impl fmt::Display for ClusterQuery {
 }
 
 impl Query for ClusterQuery {
-    fn to_url(&self) -> Result<Url> {
-        let mut url = Url::parse(GOOGLESCHOLAR_URL_BASE).unwrap();
-        let query = format!("cluster={}", self.cluster_id);
+    fn to_localized_url(&self, base: &BaseUrl, language: Language) -> Result<Url> {
+        let mut url = base.scholar_url();
+        let query = format!("cluster={}&hl={}", self.cluster_id, language.code());
         url.set_query(Some(&query));
         Ok(url)
     }
@@ -427,78 +1256,682 @@ impl ClusterQuery {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn search_query_to_url() {
-        let mut q = SearchQuery::default();
+/// Query to get list of versions of a paper cluster.
+#[derive(Clone)]
+pub struct VersionsQuery {
+    cluster_id: u64,
+    max_result_count: u32,
+    start: u32,
+}
 
-        const TEST_COUNT: u32 = DEFAULT_MAX_RESULT_COUNT + 1;
-        q.set_count(TEST_COUNT);
-        q.set_phrase("quantum theory");
-        q.set_authors("albert einstein");
-        q.set_title_only(true);
+impl fmt::Display for VersionsQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            r#"query to get list of versions of a paper of:
+  Cluster ID: {},
+max #results: {},
+      offset: {}"#,
+            self.cluster_id, self.max_result_count, self.start
+        )
+    }
+}
 
-        assert_eq!(
-            q.to_url().unwrap(),
-            Url::parse(&format!(
-                "{}?\
-                 as_q=\"quantum theory\"\
-                 &as_epq=\
-                 &as_eq=\
-                 &as_occt=title\
-                 &as_sauthors=albert%20einstein\
-                 &as_publication=\
-                 &as_ylo=\
-                 &as_yhi=\
-                 &as_vis=0\
-                 &btnG=\
-                 &hl=en\
-                 &num={}\
-                 &as_sdt=0%2C5",
-                GOOGLESCHOLAR_URL_BASE, TEST_COUNT
-            )).unwrap()
+impl Query for VersionsQuery {
+    fn to_localized_url(&self, base: &BaseUrl, language: Language) -> Result<Url> {
+        let mut url = base.scholar_url();
+        let query = format!(
+            "cluster={}&hl={}&num={}",
+            self.cluster_id,
+            language.code(),
+            self.max_result_count
         );
+        let query = append_start(query, self.start);
+        url.set_query(Some(&query));
+        Ok(url)
     }
+}
 
-    #[test]
-    fn search_query_is_valid_pass() {
-        {
-            let mut q = SearchQuery::default();
-
-            q.set_words("foo");
-            assert!(q.is_valid());
+impl VersionsQuery {
+    /// Create new VersionsQuery with `cluster_id`.
+    /// Maximum number of search result is defaulting to 5.
+    pub fn new(cluster_id: u64) -> Self {
+        Self {
+            cluster_id,
+            max_result_count: DEFAULT_MAX_RESULT_COUNT,
+            start: 0,
         }
+    }
 
-        {
-            let mut q = SearchQuery::default();
+    /// Set `max_result_count` to maximum number of versions.
+    /// The `max_result_count` will be rounded down to 10.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::VersionsQuery;
+    ///
+    /// let mut q = VersionsQuery::new(0);
+    /// q.set_count(2);
+    /// assert_eq!(q.get_count(), 2);
+    ///
+    /// q.set_count(11);
+    /// assert_eq!(q.get_count(), 10);
+    /// ```
+    pub fn set_count(&mut self, max_result_count: u32) {
+        use std::cmp;
+        self.max_result_count = cmp::min(max_result_count, MAX_RESULT_COUNT);
+    }
 
-            q.set_authors("foo");
-            assert!(q.is_valid());
-        }
+    pub fn get_count(&self) -> u32 {
+        self.max_result_count
     }
 
-    #[test]
-    fn search_query_is_valid_fail() {
-        let q = SearchQuery::default();
-        assert!(!q.is_valid());
+    /// Set `start` to the offset of the first version.
+    pub fn set_start(&mut self, start: u32) {
+        self.start = start;
     }
 
-    #[test]
-    fn citation_query_to_url() {
-        let mut q = CitationQuery::new(&format!("{}?cites=0", GOOGLESCHOLAR_URL_BASE));
+    pub fn get_start(&self) -> u32 {
+        self.start
+    }
+}
 
-        assert_eq!(
-            q.to_url().unwrap(),
-            Url::parse(&format!(
-                "{}?cites=0&hl=en&num={}",
-                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
-            )).unwrap()
-        );
+impl_paged_query!(VersionsQuery);
 
-        const TEST_COUNT: u32 = DEFAULT_MAX_RESULT_COUNT + 1;
+/// Query to get list of papers related to a paper.
+#[derive(Clone)]
+pub struct RelatedQuery {
+    cluster_id: u64,
+    max_result_count: u32,
+    start: u32,
+}
+
+impl fmt::Display for RelatedQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            r#"query to get list of papers related to a paper of:
+  Cluster ID: {},
+max #results: {},
+      offset: {}"#,
+            self.cluster_id, self.max_result_count, self.start
+        )
+    }
+}
+
+impl Query for RelatedQuery {
+    fn to_localized_url(&self, base: &BaseUrl, language: Language) -> Result<Url> {
+        let mut url = base.scholar_url();
+        let query = format!(
+            "q=related:{}:scholar.google.com/&hl={}&num={}",
+            related_id::encode(self.cluster_id),
+            language.code(),
+            self.max_result_count
+        );
+        let query = append_start(query, self.start);
+        url.set_query(Some(&query));
+        Ok(url)
+    }
+}
+
+impl RelatedQuery {
+    /// Create new RelatedQuery with `cluster_id`.
+    /// Maximum number of search result is defaulting to 5.
+    pub fn new(cluster_id: u64) -> Self {
+        Self {
+            cluster_id,
+            max_result_count: DEFAULT_MAX_RESULT_COUNT,
+            start: 0,
+        }
+    }
+
+    /// Set `max_result_count` to maximum number of related papers.
+    /// The `max_result_count` will be rounded down to 10.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::RelatedQuery;
+    ///
+    /// let mut q = RelatedQuery::new(0);
+    /// q.set_count(2);
+    /// assert_eq!(q.get_count(), 2);
+    ///
+    /// q.set_count(11);
+    /// assert_eq!(q.get_count(), 10);
+    /// ```
+    pub fn set_count(&mut self, max_result_count: u32) {
+        use std::cmp;
+        self.max_result_count = cmp::min(max_result_count, MAX_RESULT_COUNT);
+    }
+
+    pub fn get_count(&self) -> u32 {
+        self.max_result_count
+    }
+
+    /// Set `start` to the offset of the first related paper.
+    pub fn set_start(&mut self, start: u32) {
+        self.start = start;
+    }
+
+    pub fn get_start(&self) -> u32 {
+        self.start
+    }
+}
+
+impl_paged_query!(RelatedQuery);
+
+/// Query to get the cite popup of a paper, which lists links to citation export files.
+pub struct CiteQuery {
+    cluster_id: u64,
+}
+
+impl fmt::Display for CiteQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "query to get export links of a paper whose cluster ID is {}",
+            self.cluster_id,
+        )
+    }
+}
+
+impl Query for CiteQuery {
+    fn to_localized_url(&self, base: &BaseUrl, language: Language) -> Result<Url> {
+        let mut url = base.scholar_url();
+        let query = format!(
+            "q=info:{}:scholar.google.com/&output=cite&scirp=0&hl={}",
+            related_id::encode(self.cluster_id),
+            language.code()
+        );
+        url.set_query(Some(&query));
+        Ok(url)
+    }
+}
+
+impl CiteQuery {
+    pub fn new(cluster_id: u64) -> Self {
+        Self { cluster_id }
+    }
+}
+
+/// Query to get a citation export file (BibTeX, EndNote, etc.),
+/// whose URL is scraped from the cite popup.
+pub struct ExportQuery {
+    export_url: String,
+}
+
+impl fmt::Display for ExportQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "query to get a citation export file of {}", self.export_url)
+    }
+}
+
+impl Query for ExportQuery {
+    // Exported files are the same in any language.
+    fn to_localized_url(&self, base: &BaseUrl, _language: Language) -> Result<Url> {
+        let url = Url::parse(&self.export_url).map_err(|_| ErrorKind::InvalidQuery)?;
+        Ok(base.rebase(&url))
+    }
+}
+
+impl ExportQuery {
+    pub fn new(export_url: &str) -> Self {
+        Self {
+            export_url: export_url.to_owned(),
+        }
+    }
+}
+
+/// Query to get a profile of an author, with a page of the author's publications.
+#[derive(Clone)]
+pub struct AuthorProfileQuery {
+    user_id: String,
+    start: u32,
+    page_size: u32,
+}
+
+impl fmt::Display for AuthorProfileQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            r#"query to get an author profile of:
+     User ID: {},
+   page size: {},
+      offset: {}"#,
+            self.user_id, self.page_size, self.start
+        )
+    }
+}
+
+impl Query for AuthorProfileQuery {
+    fn to_localized_url(&self, base: &BaseUrl, language: Language) -> Result<Url> {
+        let mut url = base.citations_url();
+        let query = format!(
+            "user={}&hl={}&cstart={}&pagesize={}",
+            self.user_id,
+            language.code(),
+            self.start,
+            self.page_size
+        );
+        url.set_query(Some(&query));
+        Ok(url)
+    }
+}
+
+impl AuthorProfileQuery {
+    /// Create new AuthorProfileQuery with `user_id`.
+    /// Page size of publications is defaulting to 20.
+    pub fn new(user_id: &str) -> Self {
+        Self {
+            user_id: user_id.to_owned(),
+            start: 0,
+            page_size: DEFAULT_PUBLICATION_PAGE_SIZE,
+        }
+    }
+
+    /// Set `start` to the offset of the first publication in the page.
+    pub fn set_start(&mut self, start: u32) {
+        self.start = start;
+    }
+
+    pub fn get_start(&self) -> u32 {
+        self.start
+    }
+
+    /// Set `page_size` to number of publications in the page.
+    /// The `page_size` will be rounded down to 100.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scholar::request::AuthorProfileQuery;
+    ///
+    /// let mut q = AuthorProfileQuery::new("foo");
+    /// q.set_page_size(50);
+    /// assert_eq!(q.get_page_size(), 50);
+    ///
+    /// q.set_page_size(101);
+    /// assert_eq!(q.get_page_size(), 100);
+    /// ```
+    pub fn set_page_size(&mut self, page_size: u32) {
+        use std::cmp;
+        self.page_size = cmp::min(page_size, MAX_PUBLICATION_PAGE_SIZE);
+    }
+
+    pub fn get_page_size(&self) -> u32 {
+        self.page_size
+    }
+}
+
+const DEFAULT_PUBLICATION_PAGE_SIZE: u32 = 20;
+
+/// Query to search authors by name.
+pub struct AuthorSearchQuery {
+    name: String,
+}
+
+impl fmt::Display for AuthorSearchQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "query to search authors named {}", self.name)
+    }
+}
+
+impl Query for AuthorSearchQuery {
+    fn to_localized_url(&self, base: &BaseUrl, language: Language) -> Result<Url> {
+        if self.name.trim().is_empty() {
+            return Err(ErrorKind::InvalidQuery.into());
+        }
+
+        let mut url = base.citations_url();
+        let query = format!(
+            "view_op=search_authors&mauthors={}&hl={}",
+            self.name,
+            language.code()
+        );
+        url.set_query(Some(&query));
+        Ok(url)
+    }
+}
+
+impl AuthorSearchQuery {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GOOGLESCHOLAR_URL_BASE: &str = "https://scholar.google.com/scholar";
+    const GOOGLESCHOLAR_CITATIONS_URL_BASE: &str = "https://scholar.google.com/citations";
+
+    #[test]
+    fn search_query_to_url() {
+        let mut q = SearchQuery::default();
+
+        const TEST_COUNT: u32 = DEFAULT_MAX_RESULT_COUNT + 1;
+        q.set_count(TEST_COUNT);
+        q.set_phrase("quantum theory");
+        q.set_authors("albert einstein");
+        q.set_title_only(true);
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?\
+                 as_q=\"quantum theory\"\
+                 &as_epq=\
+                 &as_eq=\
+                 &as_occt=title\
+                 &as_sauthors=albert%20einstein\
+                 &as_publication=\
+                 &as_ylo=\
+                 &as_yhi=\
+                 &as_vis=0\
+                 &btnG=\
+                 &hl=en\
+                 &num={}\
+                 &as_sdt=0%2C5",
+                GOOGLESCHOLAR_URL_BASE, TEST_COUNT
+            )).unwrap()
+        );
+    }
+
+    #[test]
+    fn search_query_to_url_year_range() {
+        let mut q = SearchQuery::default();
+
+        q.set_words("quantum");
+        q.set_year_from(2015);
+        q.set_year_to(2018);
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?\
+                 as_q=quantum\
+                 &as_epq=\
+                 &as_eq=\
+                 &as_occt=any\
+                 &as_sauthors=\
+                 &as_publication=\
+                 &as_ylo=2015\
+                 &as_yhi=2018\
+                 &as_vis=0\
+                 &btnG=\
+                 &hl=en\
+                 &num={}\
+                 &as_sdt=0%2C5",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+    }
+
+    #[test]
+    fn search_query_to_url_excluded_words_publication() {
+        let mut q = SearchQuery::default();
+
+        q.set_words("quantum");
+        q.set_excluded_words("classical");
+        q.set_publication("nature");
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?\
+                 as_q=quantum\
+                 &as_epq=\
+                 &as_eq=classical\
+                 &as_occt=any\
+                 &as_sauthors=\
+                 &as_publication=nature\
+                 &as_ylo=\
+                 &as_yhi=\
+                 &as_vis=0\
+                 &btnG=\
+                 &hl=en\
+                 &num={}\
+                 &as_sdt=0%2C5",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+    }
+
+    #[test]
+    fn search_query_to_url_start() {
+        let mut q = SearchQuery::default();
+
+        q.set_words("quantum");
+        q.set_start(10);
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?\
+                 as_q=quantum\
+                 &as_epq=\
+                 &as_eq=\
+                 &as_occt=any\
+                 &as_sauthors=\
+                 &as_publication=\
+                 &as_ylo=\
+                 &as_yhi=\
+                 &as_vis=0\
+                 &btnG=\
+                 &hl=en\
+                 &num={}\
+                 &as_sdt=0%2C5\
+                 &start=10",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+    }
+
+    #[test]
+    fn search_query_to_url_collection() {
+        let mut q = SearchQuery::default();
+
+        q.set_words("quantum");
+        q.set_collection(Collection::Articles {
+            include_patents: false,
+        });
+        q.set_include_citations(false);
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?\
+                 as_q=quantum\
+                 &as_epq=\
+                 &as_eq=\
+                 &as_occt=any\
+                 &as_sauthors=\
+                 &as_publication=\
+                 &as_ylo=\
+                 &as_yhi=\
+                 &as_vis=1\
+                 &btnG=\
+                 &hl=en\
+                 &num={}\
+                 &as_sdt=1%2C5",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+
+        q.set_collection(Collection::CaseLaw(Jurisdiction::AllCourts));
+        q.set_include_citations(true);
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?\
+                 as_q=quantum\
+                 &as_epq=\
+                 &as_eq=\
+                 &as_occt=any\
+                 &as_sauthors=\
+                 &as_publication=\
+                 &as_ylo=\
+                 &as_yhi=\
+                 &as_vis=0\
+                 &btnG=\
+                 &hl=en\
+                 &num={}\
+                 &as_sdt=2006",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+
+        q.set_collection(Collection::CaseLaw(Jurisdiction::Courts(vec![33, 192])));
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?\
+                 as_q=quantum\
+                 &as_epq=\
+                 &as_eq=\
+                 &as_occt=any\
+                 &as_sauthors=\
+                 &as_publication=\
+                 &as_ylo=\
+                 &as_yhi=\
+                 &as_vis=0\
+                 &btnG=\
+                 &hl=en\
+                 &num={}\
+                 &as_sdt=4%2C33%2C192",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+    }
+
+    #[test]
+    fn collection_as_sdt_test() {
+        assert_eq!(Collection::default().as_sdt(), "0%2C5");
+        assert_eq!(
+            Collection::Articles {
+                include_patents: false,
+            }.as_sdt(),
+            "1%2C5"
+        );
+        assert_eq!(
+            Collection::CaseLaw(Jurisdiction::AllCourts).as_sdt(),
+            "2006"
+        );
+        assert_eq!(
+            Collection::CaseLaw(Jurisdiction::Courts(vec![33])).as_sdt(),
+            "4%2C33"
+        );
+    }
+
+    #[test]
+    fn search_query_to_url_sort_by_date() {
+        let mut q = SearchQuery::default();
+
+        q.set_words("quantum");
+        q.set_sort_order(SortOrder::Date);
+        q.set_start(10);
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?\
+                 as_q=quantum\
+                 &as_epq=\
+                 &as_eq=\
+                 &as_occt=any\
+                 &as_sauthors=\
+                 &as_publication=\
+                 &as_ylo=\
+                 &as_yhi=\
+                 &as_vis=0\
+                 &btnG=\
+                 &hl=en\
+                 &num={}\
+                 &as_sdt=0%2C5\
+                 &scisbd=1\
+                 &start=10",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+    }
+
+    #[test]
+    fn search_query_is_valid_pass() {
+        {
+            let mut q = SearchQuery::default();
+
+            q.set_words("foo");
+            assert!(q.is_valid());
+        }
+
+        {
+            let mut q = SearchQuery::default();
+
+            q.set_authors("foo");
+            assert!(q.is_valid());
+        }
+
+        {
+            let mut q = SearchQuery::default();
+
+            q.set_publication("foo");
+            assert!(q.is_valid());
+        }
+
+        {
+            let mut q = SearchQuery::default();
+
+            q.set_words("foo");
+            q.set_year_from(2000);
+            q.set_year_to(2000);
+            assert!(q.is_valid());
+        }
+    }
+
+    #[test]
+    fn search_query_is_valid_fail() {
+        {
+            let q = SearchQuery::default();
+            assert!(!q.is_valid());
+        }
+
+        {
+            let mut q = SearchQuery::default();
+
+            q.set_excluded_words("foo");
+            assert!(!q.is_valid());
+        }
+
+        {
+            let mut q = SearchQuery::default();
+
+            q.set_words("foo");
+            q.set_year_from(2001);
+            q.set_year_to(2000);
+            assert!(!q.is_valid());
+        }
+    }
+
+    #[test]
+    fn citation_query_to_url() {
+        let mut q = CitationQuery::new(&format!("{}?cites=0", GOOGLESCHOLAR_URL_BASE));
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?cites=0&hl=en&num={}",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+
+        const TEST_COUNT: u32 = DEFAULT_MAX_RESULT_COUNT + 1;
         q.set_count(TEST_COUNT);
 
         assert_eq!(
@@ -508,6 +1941,113 @@ mod tests {
                 GOOGLESCHOLAR_URL_BASE, TEST_COUNT
             )).unwrap()
         );
+
+        q.set_start(20);
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?cites=0&hl=en&num={}&start=20",
+                GOOGLESCHOLAR_URL_BASE, TEST_COUNT
+            )).unwrap()
+        );
+
+        q.set_sort_order(SortOrder::Date);
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?cites=0&hl=en&num={}&scisbd=1&start=20",
+                GOOGLESCHOLAR_URL_BASE, TEST_COUNT
+            )).unwrap()
+        );
+    }
+
+    #[test]
+    fn citation_query_to_url_keywords() {
+        let mut q = CitationQuery::from_cluster_id(0, &BaseUrl::default());
+
+        q.set_words("quantum");
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?cites=0&q=quantum&scipsc=1&hl=en&num={}",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+
+        q.append_phrase("phase factor");
+        q.set_authors("MV Berry");
+        q.set_start(10);
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?cites=0\
+                 &q=quantum \"phase factor\" author:\"MV Berry\"\
+                 &scipsc=1&hl=en&num={}&start=10",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+
+        let mut q = CitationQuery::from_cluster_id(0, &BaseUrl::default());
+        q.set_authors("einstein");
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?cites=0&q=author:einstein&scipsc=1&hl=en&num={}",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+    }
+
+    #[test]
+    fn citation_query_to_url_years() {
+        let mut q = CitationQuery::new(&format!(
+            "{}?cites=0&as_ylo=2000&hl=en",
+            GOOGLESCHOLAR_URL_BASE
+        ));
+
+        q.set_year_from(2017);
+        q.set_sort_order(SortOrder::Date);
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?cites=0&as_ylo=2017&hl=en&num={}&scisbd=1",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+
+        q.set_words("quantum");
+        q.set_year_to(2018);
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?cites=0&q=quantum&scipsc=1&as_ylo=2017&as_yhi=2018&hl=en&num={}&scisbd=1",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+
+        q.set_year_to(2016);
+        assert!(q.to_url().is_err());
+    }
+
+    #[test]
+    fn search_terms_shared_test() {
+        let mut search = SearchQuery::default();
+        let mut citation = CitationQuery::from_cluster_id(0, &BaseUrl::default());
+
+        search.set_words("quantum");
+        search.append_phrase("phase factor");
+        search.append_authors("berry");
+        citation.set_words("quantum");
+        citation.append_phrase("phase factor");
+        citation.append_authors("berry");
+
+        assert_eq!(search.get_terms(), citation.get_terms());
+        assert_eq!(
+            citation.get_words(),
+            &Some(String::from(r#"quantum "phase factor""#))
+        );
     }
 
     #[test]
@@ -518,9 +2058,226 @@ mod tests {
         assert_eq!(
             q.to_url().unwrap(),
             Url::parse(&format!(
-                "{}?cluster={}",
+                "{}?cluster={}&hl=en",
                 GOOGLESCHOLAR_URL_BASE, TEST_CLUSTER_ID
             )).unwrap()
         );
     }
+
+    #[test]
+    fn versions_query_to_url() {
+        const TEST_CLUSTER_ID: u64 = 999;
+        let mut q = VersionsQuery::new(TEST_CLUSTER_ID);
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?cluster={}&hl=en&num={}",
+                GOOGLESCHOLAR_URL_BASE, TEST_CLUSTER_ID, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+
+        q.set_start(10);
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?cluster={}&hl=en&num={}&start=10",
+                GOOGLESCHOLAR_URL_BASE, TEST_CLUSTER_ID, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+    }
+
+    #[test]
+    fn related_query_to_url() {
+        let q = RelatedQuery::new(11845712235651853713);
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?q=related:ke2GXYRsZKQJ:scholar.google.com/&hl=en&num={}",
+                GOOGLESCHOLAR_URL_BASE, DEFAULT_MAX_RESULT_COUNT
+            )).unwrap()
+        );
+    }
+
+    #[test]
+    fn cite_query_to_url() {
+        let q = CiteQuery::new(16499695044466828447);
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?q=info:n-S3Szmx-uQJ:scholar.google.com/&output=cite&scirp=0&hl=en",
+                GOOGLESCHOLAR_URL_BASE
+            )).unwrap()
+        );
+    }
+
+    #[test]
+    fn export_query_to_url() {
+        let url = "https://scholar.googleusercontent.com/scholar.bib?\
+                   q=info:n-S3Szmx-uQJ:scholar.google.com/&output=citation&hl=en";
+        let q = ExportQuery::new(url);
+        assert_eq!(q.to_url().unwrap(), Url::parse(url).unwrap());
+
+        assert!(ExportQuery::new("foo").to_url().is_err());
+    }
+
+    #[test]
+    fn base_url_test() {
+        assert_eq!(BaseUrl::default().to_string(), "https://scholar.google.com/");
+        assert_eq!(
+            BaseUrl::new("http://localhost:8080").unwrap().to_string(),
+            "http://localhost:8080/"
+        );
+        assert_eq!(
+            BaseUrl::new("http://localhost:8080/mock/?foo#bar")
+                .unwrap()
+                .to_string(),
+            "http://localhost:8080/mock/"
+        );
+        assert!(BaseUrl::new("ftp://scholar.google.com/").is_err());
+        assert!(BaseUrl::new("mailto:foo@example.com").is_err());
+
+        let base = BaseUrl::new("http://localhost:8080/mock/").unwrap();
+        assert_eq!(
+            base.citations_url().as_str(),
+            "http://localhost:8080/mock/citations"
+        );
+        assert_eq!(
+            base.rebase_str("https://scholar.google.co.jp/scholar?cites=0"),
+            "http://localhost:8080/mock/scholar?cites=0"
+        );
+        assert_eq!(base.rebase_str("foo"), "foo");
+    }
+
+    #[test]
+    fn query_to_url_with_base() {
+        let base = BaseUrl::regional("de").unwrap();
+
+        let mut q = SearchQuery::default();
+        q.set_words("foo");
+        assert!(
+            q.to_url_with_base(&base)
+                .unwrap()
+                .as_str()
+                .starts_with("https://scholar.google.de/scholar?")
+        );
+
+        let q = CitationQuery::new(
+            "https://scholar.google.co.jp/scholar?cites=5545735591029960915&as_sdt=2005&hl=en",
+        );
+        assert_eq!(
+            q.to_url_with_base(&base).unwrap().as_str(),
+            "https://scholar.google.de/scholar?\
+             cites=5545735591029960915&as_sdt=2005&hl=en&num=5"
+        );
+
+        // Parameters of the query are not duplicated.
+        let mut q = CitationQuery::new(
+            "https://scholar.google.com/scholar?hl=ja&cites=0&num=20&start=10&scisbd=1",
+        );
+        q.set_start(20);
+        assert_eq!(
+            q.to_url_with_base(&base).unwrap().as_str(),
+            "https://scholar.google.de/scholar?cites=0&hl=en&num=5&start=20"
+        );
+
+        assert_eq!(
+            ClusterQuery::new(0)
+                .to_url_with_base(&base)
+                .unwrap()
+                .as_str(),
+            "https://scholar.google.de/scholar?cluster=0&hl=en"
+        );
+        assert_eq!(
+            AuthorSearchQuery::new("foo")
+                .to_url_with_base(&base)
+                .unwrap()
+                .as_str(),
+            "https://scholar.google.de/citations?view_op=search_authors&mauthors=foo&hl=en"
+        );
+
+        // Exported files are hosted by another domain.
+        let url = "https://scholar.googleusercontent.com/scholar.bib?q=info:0:scholar.google.com/";
+        assert_eq!(
+            ExportQuery::new(url)
+                .to_url_with_base(&base)
+                .unwrap()
+                .as_str(),
+            url
+        );
+    }
+
+    #[test]
+    fn query_to_localized_url() {
+        use language::Language;
+
+        fn to_url<Q: Query>(q: &Q, language: Language) -> String {
+            q.to_localized_url(&BaseUrl::default(), language)
+                .unwrap()
+                .into_string()
+        }
+
+        let base = BaseUrl::default();
+
+        let mut q = SearchQuery::default();
+        q.set_words("foo");
+        assert!(to_url(&q, Language::Japanese).contains("&hl=ja&"));
+        assert_eq!(
+            to_url(&CitationQuery::from_cluster_id(0, &base), Language::German),
+            "https://scholar.google.com/scholar?cites=0&hl=de&num=5"
+        );
+        assert_eq!(
+            to_url(&ClusterQuery::new(0), Language::Japanese),
+            "https://scholar.google.com/scholar?cluster=0&hl=ja"
+        );
+        assert_eq!(
+            to_url(&AuthorProfileQuery::new("lpTL7HwAAAAJ"), Language::German),
+            "https://scholar.google.com/citations?user=lpTL7HwAAAAJ&hl=de&cstart=0&pagesize=20"
+        );
+
+        // Exported files are not localized.
+        let url = "https://scholar.googleusercontent.com/scholar.bib?q=info:0:scholar.google.com/";
+        assert_eq!(to_url(&ExportQuery::new(url), Language::German), url);
+    }
+
+    #[test]
+    fn author_profile_query_to_url() {
+        let mut q = AuthorProfileQuery::new("lpTL7HwAAAAJ");
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?user=lpTL7HwAAAAJ&hl=en&cstart=0&pagesize=20",
+                GOOGLESCHOLAR_CITATIONS_URL_BASE
+            )).unwrap()
+        );
+
+        q.set_start(100);
+        q.set_page_size(100);
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?user=lpTL7HwAAAAJ&hl=en&cstart=100&pagesize=100",
+                GOOGLESCHOLAR_CITATIONS_URL_BASE
+            )).unwrap()
+        );
+    }
+
+    #[test]
+    fn author_search_query_to_url() {
+        let q = AuthorSearchQuery::new("jean zinn-justin");
+
+        assert_eq!(
+            q.to_url().unwrap(),
+            Url::parse(&format!(
+                "{}?view_op=search_authors&mauthors=jean%20zinn-justin&hl=en",
+                GOOGLESCHOLAR_CITATIONS_URL_BASE
+            )).unwrap()
+        );
+
+        assert!(AuthorSearchQuery::new(" ").to_url().is_err());
+    }
 }
@@ -0,0 +1,159 @@
+//! Retry requests failed transiently, with exponential backoff.
+
+use std::cmp;
+use std::fmt;
+use std::time::Duration;
+
+/// How to retry failed requests.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries of a request. 0 for no retry.
+    pub max_retries: u32,
+    /// Wait before the first retry of a transient failure.
+    pub initial_backoff: Duration,
+    /// Factor by which the wait is multiplied on each retry.
+    pub multiplier: u32,
+    /// Upper bound of the wait before a retry of a transient failure.
+    pub max_backoff: Duration,
+    /// What to do when Google Scholar blocked the request.
+    pub on_blocked: BlockedAction,
+}
+
+/// What to do when Google Scholar blocked a request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockedAction {
+    /// Fail with `ErrorKind::Blocked` immediately.
+    Stop,
+    /// Wait for the duration, then retry within `max_retries`.
+    Wait(Duration),
+}
+
+impl Default for RetryPolicy {
+    /// Up to 3 retries, waiting 1, 2 and 4 seconds, and stop if blocked.
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_secs(1),
+            multiplier: 2,
+            max_backoff: Duration::from_secs(60),
+            on_blocked: BlockedAction::Stop,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry, and stop if blocked.
+    pub fn no_retry() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Wait before the `retry`-th retry (0-based) for `reason`.
+    /// `None` if no more retry is allowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use scholar::retry::{RetryPolicy, RetryReason};
+    ///
+    /// let policy = RetryPolicy::default();
+    /// let reason = RetryReason::HttpStatus(503);
+    /// assert_eq!(policy.backoff(0, &reason), Some(Duration::from_secs(1)));
+    /// assert_eq!(policy.backoff(2, &reason), Some(Duration::from_secs(4)));
+    /// assert_eq!(policy.backoff(3, &reason), None);
+    ///
+    /// // Blocked requests are not retried by default.
+    /// assert_eq!(policy.backoff(0, &RetryReason::Blocked), None);
+    /// ```
+    pub fn backoff(&self, retry: u32, reason: &RetryReason) -> Option<Duration> {
+        if retry >= self.max_retries {
+            return None;
+        }
+
+        match *reason {
+            RetryReason::Blocked => match self.on_blocked {
+                BlockedAction::Stop => None,
+                BlockedAction::Wait(wait) => Some(wait),
+            },
+            _ => {
+                let factor = self.multiplier.checked_pow(retry).unwrap_or(u32::MAX);
+                let wait = self.initial_backoff
+                    .checked_mul(factor)
+                    .unwrap_or(self.max_backoff);
+                Some(cmp::min(wait, self.max_backoff))
+            }
+        }
+    }
+}
+
+/// Why a request is retried.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RetryReason {
+    /// Google Scholar blocked the request.
+    Blocked,
+    /// Google Scholar responded with a server error status.
+    HttpStatus(u16),
+    /// The request failed before getting a response, e.g. timed out.
+    Transport(String),
+}
+
+impl fmt::Display for RetryReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RetryReason::Blocked => write!(f, "request blocked"),
+            RetryReason::HttpStatus(code) => write!(f, "HTTP status {}", code),
+            RetryReason::Transport(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// A retry about to happen, passed to the observer of `ScholarClient`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryEvent {
+    /// URL of the request.
+    pub url: String,
+    /// 1 for the first retry.
+    pub retry: u32,
+    pub reason: RetryReason,
+    /// Wait before the retry.
+    pub wait: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_test() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(500),
+            multiplier: 3,
+            max_backoff: Duration::from_secs(10),
+            on_blocked: BlockedAction::Wait(Duration::from_secs(600)),
+        };
+        let reason = RetryReason::Transport(String::from("timed out"));
+
+        assert_eq!(policy.backoff(0, &reason), Some(Duration::from_millis(500)));
+        assert_eq!(policy.backoff(1, &reason), Some(Duration::from_millis(1500)));
+        assert_eq!(policy.backoff(2, &reason), Some(Duration::from_millis(4500)));
+        assert_eq!(policy.backoff(3, &reason), Some(Duration::from_secs(10)));
+        assert_eq!(policy.backoff(9, &reason), Some(Duration::from_secs(10)));
+        assert_eq!(policy.backoff(10, &reason), None);
+
+        assert_eq!(
+            policy.backoff(5, &RetryReason::Blocked),
+            Some(Duration::from_secs(600))
+        );
+    }
+
+    #[test]
+    fn no_retry_test() {
+        let policy = RetryPolicy::no_retry();
+        assert_eq!(policy.backoff(0, &RetryReason::HttpStatus(500)), None);
+        assert_eq!(policy.backoff(0, &RetryReason::Blocked), None);
+    }
+}
@@ -1,5 +1,6 @@
 //! Scrape HTML document to get information of papers.
 
+use std::convert::TryFrom;
 use std::io;
 use std::ops::Deref;
 
@@ -7,8 +8,12 @@ use select::document::Document;
 use select::node::Node;
 use select::predicate::{Attr, Class, Name, Predicate, Text};
 
-use paper::Paper;
+use author::{AuthorProfile, AuthorSummary, CitationMetrics, MetricValues, Publication, YearlyCitations};
+use paper::{Author, BibtexEntry, ExportLinks, FullTextLink, Paper, PaperKind, Version};
 use errors::*;
+use language::Language;
+use related_id;
+use request::BaseUrl;
 
 pub trait PapersDocument {
     /// Scrape listed papers.
@@ -16,46 +21,71 @@ pub trait PapersDocument {
 
     /// Determine whether Google Scholar blocked your request.
     fn is_blocked(&self) -> bool;
+
+    /// Scrape the approximate number of results, like 1960000 of "About 1,960,000 results".
+    /// `None` if the number is not shown.
+    fn scrape_result_count(&self) -> Option<u64>;
 }
 
+// A page of scholar.google.com in English.
 impl PapersDocument for Document {
     fn scrape_papers(&self) -> Result<Vec<Paper>> {
-        // <div id="gs_res_ccl_mid">
-        //   <div class="gs_ri">
-        //     each paper
-        //   </div>
-        //   <div class="gs_ri">
-        //     each paper
-        //   </div>
-        //   ...
-        // </div>
-
-        let paper_nodes = {
-            let pos = Attr("id", "gs_res_ccl_mid").descendant(Class("gs_ri"));
-            self.find(pos)
-        };
+        scrape_papers(self, &BaseUrl::default(), Language::default())
+    }
 
-        let mut papers = Vec::with_capacity(10);
-        for n in paper_nodes {
-            papers.push(scrape_paper_one(&n)?);
-        }
+    fn is_blocked(&self) -> bool {
+        is_blocked(self, Language::default())
+    }
 
-        Ok(papers)
+    fn scrape_result_count(&self) -> Option<u64> {
+        scrape_result_count(self, Language::default())
     }
+}
 
-    fn is_blocked(&self) -> bool {
-        let pos = Name("div").child(Name("div")).child(Text);
-        let blocked_node = self.find(pos).filter(|n: &Node| {
-            n.as_text()
-                .and_then(|s| {
-                    Some(s.contains(
-                        "Our systems have detected unusual traffic from your computer network.",
-                    ))
-                })
-                .unwrap_or(false)
-        });
-        blocked_node.count() > 0
+fn is_blocked(doc: &Document, language: Language) -> bool {
+    let pos = Name("div").child(Name("div")).child(Text);
+    let blocked_node = doc.find(pos).filter(|n: &Node| {
+        n.as_text()
+            .map(|s| s.contains(language.texts().block_message))
+            .unwrap_or(false)
+    });
+    blocked_node.count() > 0
+}
+
+fn scrape_result_count(doc: &Document, language: Language) -> Option<u64> {
+    // <div id="gs_ab_md">
+    //   <div class="gs_ab_mdw">About 1,960,000 results (<b>0.32</b> sec)</div>
+    // </div>
+
+    let pos = Attr("id", "gs_ab_md").child(Class("gs_ab_mdw"));
+    doc.find(pos)
+        .filter_map(|n| parse_result_count(&n.text(), language))
+        .next()
+}
+
+// Scrape papers listed in `doc`, with `citation_url` and `versions_url` under `base`.
+fn scrape_papers(doc: &Document, base: &BaseUrl, language: Language) -> Result<Vec<Paper>> {
+    // <div id="gs_res_ccl_mid">
+    //   <div class="gs_ri">
+    //     each paper
+    //   </div>
+    //   <div class="gs_ri">
+    //     each paper
+    //   </div>
+    //   ...
+    // </div>
+
+    let paper_nodes = {
+        let pos = Attr("id", "gs_res_ccl_mid").descendant(Class("gs_ri"));
+        doc.find(pos)
+    };
+
+    let mut papers = Vec::with_capacity(10);
+    for n in paper_nodes {
+        papers.push(scrape_paper_one(&n, base, language)?);
     }
+
+    Ok(papers)
 }
 
 macro_rules! impl_from_to_document {
@@ -64,20 +94,47 @@ macro_rules! impl_from_to_document {
             type Target = Document;
 
             fn deref(&self) -> &Self::Target {
-                &self.0
+                &self.doc
             }
         }
 
         impl<'a> From<&'a str> for $struct {
             fn from(s: &str) -> Self {
                 let doc = Document::from(s);
-                $struct(doc)
+                Self::new(doc)
             }
         }
 
         impl $struct {
+            /// Create new document of a page of scholar.google.com in English.
             pub fn new(doc: Document) -> Self {
-                $struct(doc)
+                $struct {
+                    doc,
+                    base_url: BaseUrl::default(),
+                    language: Language::default(),
+                }
+            }
+
+            /// Set the base URL of Google Scholar which the page is got from.
+            /// URLs of scraped papers are built under it.
+            pub fn with_base_url(mut self, base_url: &BaseUrl) -> Self {
+                self.base_url = base_url.clone();
+                self
+            }
+
+            pub fn base_url(&self) -> &BaseUrl {
+                &self.base_url
+            }
+
+            /// Set the interface language which the page is requested in.
+            /// Localized texts of the page are scraped in this language.
+            pub fn with_language(mut self, language: Language) -> Self {
+                self.language = language;
+                self
+            }
+
+            pub fn language(&self) -> Language {
+                self.language
             }
 
             // like Document::from_read()
@@ -89,6 +146,25 @@ macro_rules! impl_from_to_document {
     }
 }
 
+// Implement `PapersDocument` by the base URL and the language of the document.
+macro_rules! impl_papers_document {
+    ($struct: ident) => {
+        impl PapersDocument for $struct {
+            fn scrape_papers(&self) -> Result<Vec<Paper>> {
+                scrape_papers(self, &self.base_url, self.language)
+            }
+
+            fn is_blocked(&self) -> bool {
+                is_blocked(self, self.language)
+            }
+
+            fn scrape_result_count(&self) -> Option<u64> {
+                scrape_result_count(self, self.language)
+            }
+        }
+    }
+}
+
 macro_rules! try_html_bad {
     ($a: expr) => { $a.ok_or(ErrorKind::BadHtml)? }
 }
@@ -97,11 +173,25 @@ macro_rules! try_html_found {
     ($a: expr) => { $a.ok_or(ErrorKind::ResultNotFount)? }
 }
 
-pub struct SearchDocument(Document);
+macro_rules! try_bibtex_bad {
+    ($a: expr) => { $a.ok_or(ErrorKind::BadBibtex)? }
+}
+
+pub struct SearchDocument {
+    doc: Document,
+    base_url: BaseUrl,
+    language: Language,
+}
 impl_from_to_document!(SearchDocument);
+impl_papers_document!(SearchDocument);
 
-pub struct CitationDocument(Document);
+pub struct CitationDocument {
+    doc: Document,
+    base_url: BaseUrl,
+    language: Language,
+}
 impl_from_to_document!(CitationDocument);
+impl_papers_document!(CitationDocument);
 
 impl CitationDocument {
     pub fn scrape_target_paper_with_citers(&self) -> Result<Paper> {
@@ -136,11 +226,15 @@ impl CitationDocument {
             parse_cluster_id(id_url)?
         };
 
-        Ok(Paper::new(&title, cluster_id))
+        Ok(Paper::with_base_url(&title, cluster_id, &self.base_url))
     }
 }
 
-pub struct ClusterDocument(Document);
+pub struct ClusterDocument {
+    doc: Document,
+    base_url: BaseUrl,
+    language: Language,
+}
 impl_from_to_document!(ClusterDocument);
 
 impl ClusterDocument {
@@ -149,41 +243,490 @@ impl ClusterDocument {
             let pos = Attr("id", "gs_res_ccl_mid").descendant(Class("gs_ri"));
             try_html_found!(self.find(pos).nth(0))
         };
-        scrape_paper_one(&paper_node)
+        scrape_paper_one(&paper_node, &self.base_url, self.language)
+    }
+}
+
+pub struct VersionsDocument {
+    doc: Document,
+    base_url: BaseUrl,
+    language: Language,
+}
+impl_from_to_document!(VersionsDocument);
+
+impl VersionsDocument {
+    /// Scrape listed versions of a paper.
+    pub fn scrape_versions(&self) -> Result<Vec<Version>> {
+        // Same format as search results,
+        // though footer of each version may not exist.
+
+        let version_nodes = {
+            let pos = Attr("id", "gs_res_ccl_mid").descendant(Class("gs_ri"));
+            self.find(pos)
+        };
+
+        let versions = version_nodes
+            .map(|n| scrape_version_one(&n, self.language))
+            .collect();
+        Ok(versions)
+    }
+}
+
+pub struct CiteDocument {
+    doc: Document,
+    base_url: BaseUrl,
+    language: Language,
+}
+impl_from_to_document!(CiteDocument);
+
+impl CiteDocument {
+    /// Scrape links to citation export files.
+    pub fn scrape_export_links(&self) -> Result<ExportLinks> {
+        // <div id="gs_citi">
+        //   <a class="gs_citi" href="https://scholar.googleusercontent.com/scholar.bib?...">
+        //     BibTeX
+        //   </a>
+        //   <a class="gs_citi" href="...">EndNote</a>
+        //   <a class="gs_citi" href="...">RefMan</a>
+        //   <a class="gs_citi" href="...">RefWorks</a>
+        // </div>
+
+        let link_nodes = {
+            let pos = Attr("id", "gs_citi").child(Name("a"));
+            self.find(pos).collect::<Vec<_>>()
+        };
+
+        if link_nodes.is_empty() {
+            return Err(ErrorKind::ResultNotFount.into());
+        }
+
+        let mut links = ExportLinks::default();
+        for n in link_nodes {
+            let url = Some(try_html_bad!(n.attr("href")).to_owned());
+            match n.text().trim() {
+                "BibTeX" => links.bibtex = url,
+                "EndNote" => links.endnote = url,
+                "RefMan" => links.refman = url,
+                "RefWorks" => links.refworks = url,
+                _ => {}
+            }
+        }
+
+        Ok(links)
+    }
+}
+
+pub struct AuthorProfileDocument {
+    doc: Document,
+    base_url: BaseUrl,
+    language: Language,
+}
+impl_from_to_document!(AuthorProfileDocument);
+
+impl AuthorProfileDocument {
+    /// Scrape the author profile, with publications listed in the page.
+    pub fn scrape_profile(&self) -> Result<AuthorProfile> {
+        // <div id="gsc_prf_i">
+        //   <div id="gsc_prf_in">name</div>
+        //   <div class="gsc_prf_il">affiliation</div>
+        //   <div class="gsc_prf_il" id="gsc_prf_ivh">
+        //     Verified email at example.edu - <a>Homepage</a>
+        //   </div>
+        //   <div class="gsc_prf_il" id="gsc_prf_int">
+        //     <a class="gsc_prf_inta">interest</a>, <a class="gsc_prf_inta">interest</a>
+        //   </div>
+        // </div>
+
+        let name = {
+            let n = try_html_found!(self.find(Attr("id", "gsc_prf_in")).next());
+            n.text().trim().to_owned()
+        };
+
+        let affiliation = self.find(Attr("id", "gsc_prf_i").child(Class("gsc_prf_il")))
+            .find(|n| n.attr("id").is_none())
+            .map(|n| n.text().trim().to_owned())
+            .filter(|s| !s.is_empty());
+
+        let email_domain = self.find(Attr("id", "gsc_prf_ivh"))
+            .next()
+            .and_then(|n| parse_email_domain(&n.text(), self.language));
+
+        let interests = self.find(Attr("id", "gsc_prf_int").child(Name("a")))
+            .map(|n| n.text().trim().to_owned())
+            .collect();
+
+        Ok(AuthorProfile {
+            name,
+            affiliation,
+            interests,
+            email_domain,
+            metrics: self.scrape_citation_metrics(),
+            citation_histogram: self.scrape_citation_histogram(),
+            publications: self.scrape_publications()?,
+        })
+    }
+
+    /// Scrape publications listed in the page.
+    pub fn scrape_publications(&self) -> Result<Vec<Publication>> {
+        // <tbody id="gsc_a_b">
+        //   <tr class="gsc_a_tr">
+        //     each publication
+        //   </tr>
+        //   ...
+        // </tbody>
+
+        self.find(Attr("id", "gsc_a_b").child(Class("gsc_a_tr")))
+            .map(|n| scrape_publication_one(&n, &self.base_url))
+            .collect()
+    }
+
+    fn scrape_citation_metrics(&self) -> Option<CitationMetrics> {
+        // <table id="gsc_rsb_st">
+        //   <thead>
+        //     <tr><th class="gsc_rsb_sth"></th><th>All</th><th>Since 2013</th></tr>
+        //   </thead>
+        //   <tbody>
+        //     <tr><td>Citations</td><td class="gsc_rsb_std">0</td><td class="gsc_rsb_std">0</td></tr>
+        //     <tr><td>h-index</td>...</tr>
+        //     <tr><td>i10-index</td>...</tr>
+        //   </tbody>
+        // </table>
+
+        let since_year = self.find(Attr("id", "gsc_rsb_st").descendant(Class("gsc_rsb_sth")))
+            .nth(2)
+            .and_then(|n| parse_since_year(&n.text()));
+
+        let values = self.find(Attr("id", "gsc_rsb_st").descendant(Class("gsc_rsb_std")))
+            .map(|n| n.text().trim().parse().ok())
+            .collect::<Option<Vec<u32>>>()?;
+        if values.len() != 6 {
+            return None;
+        }
+
+        let metric = |row: usize| MetricValues {
+            all: values[row * 2],
+            recent: values[row * 2 + 1],
+        };
+
+        Some(CitationMetrics {
+            since_year,
+            citations: metric(0),
+            h_index: metric(1),
+            i10_index: metric(2),
+        })
+    }
+
+    fn scrape_citation_histogram(&self) -> Vec<YearlyCitations> {
+        // <div class="gsc_md_hist_b">
+        //   <span class="gsc_g_t">2011</span>
+        //   <span class="gsc_g_t">2012</span>
+        //   ...
+        //   <a class="gsc_g_a" style="...;z-index:2"><span class="gsc_g_al">10</span></a>
+        //   <a class="gsc_g_a" style="...;z-index:1"><span class="gsc_g_al">20</span></a>
+        // </div>
+        //
+        // Bars of years without citations are omitted.
+        // z-index of a bar is its position counted from the last year.
+
+        let years = self.find(Class("gsc_g_t"))
+            .filter_map(|n| n.text().trim().parse().ok())
+            .collect::<Vec<u32>>();
+
+        let mut counts = vec![0; years.len()];
+        for bar in self.find(Class("gsc_g_a")) {
+            let pos = bar.attr("style").and_then(parse_z_index);
+            let count = bar.find(Class("gsc_g_al"))
+                .next()
+                .and_then(|n| n.text().trim().parse().ok());
+
+            if let (Some(pos), Some(count)) = (pos, count) {
+                if pos >= 1 && pos <= years.len() {
+                    counts[years.len() - pos] = count;
+                }
+            }
+        }
+
+        years
+            .into_iter()
+            .zip(counts)
+            .map(|(year, count)| YearlyCitations { year, count })
+            .collect()
+    }
+}
+
+pub struct AuthorSearchDocument {
+    doc: Document,
+    base_url: BaseUrl,
+    language: Language,
+}
+impl_from_to_document!(AuthorSearchDocument);
+
+impl AuthorSearchDocument {
+    /// Scrape listed authors.
+    pub fn scrape_authors(&self) -> Result<Vec<AuthorSummary>> {
+        // <div class="gsc_1usr">
+        //   <div class="gsc_1usr_text">
+        //     <h3 class="gsc_1usr_name">
+        //       <a href="/citations?user=xxxxxxxxxxxx&hl=en">name</a>
+        //     </h3>
+        //     <div class="gsc_1usr_aff">affiliation</div>
+        //     <div class="gsc_1usr_eml">Verified email at example.edu</div>
+        //     <div class="gsc_1usr_cby">Cited by 0</div>
+        //     <div class="gsc_1usr_int">
+        //       <a class="gsc_co_int">interest</a> <a class="gsc_co_int">interest</a>
+        //     </div>
+        //   </div>
+        // </div>
+        // <div class="gsc_1usr">
+        //   ...
+        // </div>
+
+        self.find(Class("gsc_1usr"))
+            .map(|n| scrape_author_summary_one(&n))
+            .collect()
+    }
+}
+
+fn scrape_author_summary_one(node: &Node) -> Result<AuthorSummary> {
+    let name_node = {
+        let pos = Class("gsc_1usr_name").child(Name("a"));
+        try_html_bad!(node.find(pos).next())
+    };
+    let name = name_node.text().trim().to_owned();
+    let user_id = parse_user_id(try_html_bad!(name_node.attr("href")))?;
+
+    let affiliation = node.find(Class("gsc_1usr_aff"))
+        .next()
+        .map(|n| n.text().trim().to_owned())
+        .filter(|s| !s.is_empty());
+
+    let citation_count = match node.find(Class("gsc_1usr_cby")).next() {
+        Some(n) => Some(parse_citation_count(&n.text())?),
+        None => None,
+    };
+
+    let interests = node.find(Class("gsc_1usr_int").child(Name("a")))
+        .map(|n| n.text().trim().to_owned())
+        .collect();
+
+    Ok(AuthorSummary {
+        user_id,
+        name,
+        affiliation,
+        citation_count,
+        interests,
+    })
+}
+
+fn scrape_publication_one(node: &Node, base: &BaseUrl) -> Result<Publication> {
+    // <tr class="gsc_a_tr">
+    //   <td class="gsc_a_t">
+    //     <a class="gsc_a_at" href="/citations?view_op=view_citation&...">title</a>
+    //     <div class="gs_gray">authors</div>
+    //     <div class="gs_gray">venue<span class="gs_oph">, year</span></div>
+    //   </td>
+    //   <td class="gsc_a_c">
+    //     <a class="gsc_a_ac" href="https://scholar.google.com/scholar?cites=0">citation count</a>
+    //   </td>
+    //   <td class="gsc_a_y"><span class="gsc_a_h">year</span></td>
+    // </tr>
+
+    let title_node = try_html_bad!(node.find(Class("gsc_a_at")).next());
+    let title = title_node.text().trim().to_owned();
+    let link = title_node.attr("href").and_then(|href| {
+        let citations_url = base.citations_url();
+        citations_url.join(href).ok().map(|url| url.as_str().to_owned())
+    });
+
+    let gray_nodes = node.find(Class("gs_gray")).collect::<Vec<_>>();
+    let authors = gray_nodes
+        .first()
+        .map(|n| n.text().trim().to_owned())
+        .filter(|s| !s.is_empty());
+    let venue = gray_nodes
+        .get(1)
+        .map(|n| {
+            n.children()
+                .filter(|c| c.name() != Some("span"))
+                .map(|c| c.text())
+                .collect::<String>()
+                .trim()
+                .to_owned()
+        })
+        .filter(|s| !s.is_empty());
+
+    let (cluster_id, citation_count) = match node.find(Class("gsc_a_ac")).next() {
+        Some(n) => (
+            n.attr("href").and_then(|url| parse_cluster_id(url).ok()),
+            n.text().trim().parse().unwrap_or(0),
+        ),
+        None => (None, 0),
+    };
+
+    let year = node.find(Class("gsc_a_y").descendant(Name("span")))
+        .next()
+        .and_then(|n| n.text().trim().parse().ok());
+
+    Ok(Publication {
+        title,
+        link,
+        authors,
+        venue,
+        year,
+        cluster_id,
+        citation_count,
+    })
+}
+
+/// Scrape a BibTeX file exported by Google Scholar as an entry of the paper of `cluster_id`.
+pub fn scrape_bibtex(cluster_id: u64, bib: &str) -> Result<BibtexEntry> {
+    // @book{zinn1996quantum,
+    //   title={Quantum field theory and critical phenomena},
+    //   author={Zinn-Justin, Jean},
+    //   ...
+    // }
+
+    use regex::Regex;
+
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^\s*@(\w+)\s*\{\s*([^,\s]*)\s*,").unwrap();
+    }
+
+    let caps = try_bibtex_bad!(RE.captures(bib));
+    let entry_type = caps[1].to_lowercase();
+    let key = caps[2].to_owned();
+
+    let mut fields = vec![];
+    let mut rest = &bib[caps.get(0).unwrap().end()..];
+    loop {
+        let pos = try_bibtex_bad!(rest.find(|c: char| c != ',' && !c.is_whitespace()));
+        rest = &rest[pos..];
+        if rest.starts_with('}') {
+            break;
+        }
+
+        let eq_pos = try_bibtex_bad!(rest.find('='));
+        let name = rest[..eq_pos].trim().to_lowercase();
+        if name.is_empty() {
+            return Err(ErrorKind::BadBibtex.into());
+        }
+
+        let (value, r) = try_bibtex_bad!(split_bibtex_value(rest[eq_pos + 1..].trim()));
+        fields.push((name, value.to_owned()));
+        rest = r;
+    }
+
+    Ok(BibtexEntry {
+        cluster_id,
+        entry_type,
+        key,
+        fields,
+    })
+}
+
+// Split `{value} rest` or `"value" rest` into the value and rest, respecting nested braces.
+// A bare value like a number ends at ',' or '}'.
+fn split_bibtex_value(s: &str) -> Option<(&str, &str)> {
+    let open = s.chars().next()?;
+    if open != '{' && open != '"' {
+        let end = s.find(&[',', '}'][..])?;
+        return Some((s[..end].trim(), &s[end..]));
+    }
+
+    let mut depth = 0;
+    for (i, c) in s.char_indices().skip(1) {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 && open == '{' => return Some((&s[1..i], &s[i + 1..])),
+            '}' => depth -= 1,
+            '"' if depth == 0 && open == '"' => return Some((&s[1..i], &s[i + 1..])),
+            _ => {}
+        }
     }
+
+    None
 }
 
 struct ArticleTitle {
     title: String,
     link: Option<String>,
+    kind: Option<PaperKind>,
 }
 
 struct ArticleHeader {
+    authors: Vec<Author>,
+    venue: Option<String>,
     year: Option<u32>,
+    publisher: Option<String>,
+    host: Option<String>,
 }
 
 struct ArticleFooter {
     cluster_id: u64,
     citation_count: u32,
+    version_count: Option<u32>,
 }
 
-fn scrape_paper_one(node: &Node) -> Result<Paper> {
-    let ArticleTitle { title, link } = scrape_article_title(node);
-    let ArticleHeader { year } = scrape_article_header(node);
+fn scrape_paper_one(node: &Node, base: &BaseUrl, language: Language) -> Result<Paper> {
+    let ArticleTitle { title, link, kind } = scrape_article_title(node, language);
+    let ArticleHeader {
+        authors,
+        venue,
+        year,
+        publisher,
+        host,
+    } = scrape_article_header(node);
+    let snippet = scrape_article_snippet(node);
+    let days_ago = scrape_article_age(node);
+    let full_text_links = scrape_full_text_links(node, language);
     let ArticleFooter {
         cluster_id,
         citation_count,
+        version_count,
     } = scrape_article_footer(node)?;
 
-    let mut paper = Paper::new(&title, cluster_id);
+    let mut paper = Paper::with_base_url(&title, cluster_id, base);
     paper.link = link;
+    paper.kind = kind;
+    paper.authors = authors;
+    paper.venue = venue;
     paper.year = year;
+    paper.publisher = publisher;
+    paper.host = host;
+    paper.snippet = snippet;
+    paper.days_ago = days_ago;
+    paper.full_text_links = full_text_links;
     paper.citation_count = Some(citation_count);
+    if version_count.is_some() {
+        paper.version_count = version_count;
+        paper.versions_url = Some(Paper::cluster_id_to_versions_url(base, cluster_id));
+    }
 
     Ok(paper)
 }
 
-fn scrape_article_title(node: &Node) -> ArticleTitle {
+fn scrape_version_one(node: &Node, language: Language) -> Version {
+    let ArticleTitle { title, link, kind } = scrape_article_title(node, language);
+    let ArticleHeader {
+        venue,
+        year,
+        publisher,
+        host,
+        ..
+    } = scrape_article_header(node);
+
+    Version {
+        title,
+        link,
+        kind,
+        venue,
+        year,
+        publisher,
+        host,
+    }
+}
+
+fn scrape_article_title(node: &Node, language: Language) -> ArticleTitle {
     // There are (at least) two formats.
     //
     // 1. Link to a paper or something:
@@ -198,6 +741,7 @@ fn scrape_article_title(node: &Node) -> ArticleTitle {
     // </h3>
     //
     // 'span' may not exists.
+    // 'span' contains a document type tag like '[PDF]', '[BOOK]' or '[CITATION]'.
     //
     // 2. Not a link:
     //
@@ -208,6 +752,13 @@ fn scrape_article_title(node: &Node) -> ArticleTitle {
     //   title of paper or something
     // </h3>
 
+    let kind = {
+        let pos = Class("gs_rt").child(Name("span"));
+        node.find(pos)
+            .next()
+            .and_then(|n| parse_paper_kind(&n.text(), language).ok())
+    };
+
     if let Some(n) = {
         let pos = Class("gs_rt").child(Name("a"));
         node.find(pos).nth(0)
@@ -216,6 +767,7 @@ fn scrape_article_title(node: &Node) -> ArticleTitle {
         ArticleTitle {
             title: n.text(),
             link: n.attr("href").map(ToOwned::to_owned),
+            kind,
         }
     } else {
         // 2. Not a link
@@ -239,10 +791,36 @@ fn scrape_article_title(node: &Node) -> ArticleTitle {
         ArticleTitle {
             title: concated_text,
             link: None,
+            kind,
         }
     }
 }
 
+fn parse_paper_kind(text: &str, language: Language) -> Result<PaperKind> {
+    use regex::Regex;
+
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"\[(\w+)\]").unwrap();
+    }
+
+    let tag = {
+        let caps = try_html_bad!(RE.captures(text));
+        try_html_bad!(caps.get(1)).as_str()
+    };
+
+    // Book and citation tags are localized.
+    let texts = language.texts();
+    let kind = match tag {
+        "HTML" => PaperKind::Html,
+        "PDF" => PaperKind::Pdf,
+        _ if tag == texts.book_tag => PaperKind::Book,
+        _ if tag == texts.citation_tag => PaperKind::Citation,
+        _ => PaperKind::Other(tag.to_owned()),
+    };
+
+    Ok(kind)
+}
+
 fn scrape_article_header(node: &Node) -> ArticleHeader {
     // There are (at least) two formats for publishment information:
     //
@@ -268,6 +846,11 @@ fn scrape_article_header(node: &Node) -> ArticleHeader {
     // <div class="gs_a">
     //   <a href="/citations?user=0">author</a> - journal etc., year - journal etc.
     // </div>
+    //
+    // Authors are separated by ',', and the list may be truncated with '…'.
+    // The second 'journal etc.' is treated as venue, and the third as publisher.
+    // Google Scholar shows the domain hosting the paper, like "cds.cern.ch", in place of
+    // unknown publisher, which is treated as host.
 
     let year_node = {
         let pos = Class("gs_a").descendant(Text);
@@ -278,100 +861,451 @@ fn scrape_article_header(node: &Node) -> ArticleHeader {
     };
     let year = year_node.map(|n| parse_year(&n.text()).unwrap());
 
-    ArticleHeader { year }
+    let header_text = {
+        let pos = Class("gs_a");
+        node.find(pos)
+            .next()
+            .map(|n| n.text().replace('\u{a0}', " "))
+            .unwrap_or_default()
+    };
+    let mut parts = header_text.split(" - ").map(str::trim);
+
+    let authors = {
+        let user_ids = {
+            let pos = Class("gs_a").child(Name("a"));
+            node.find(pos)
+                .filter_map(|n| {
+                    let user_id = parse_user_id(n.attr("href")?).ok()?;
+                    Some((n.text(), user_id))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        parts
+            .next()
+            .unwrap_or("")
+            .split(',')
+            .map(trim_ellipsis)
+            .filter(|name| !name.is_empty())
+            .map(|name| Author {
+                name: name.to_owned(),
+                user_id: user_ids
+                    .iter()
+                    .find(|pair| pair.0 == name)
+                    .map(|pair| pair.1.clone()),
+            })
+            .collect()
+    };
+
+    let venue = parts.next().and_then(parse_venue);
+    let (publisher, host) = match parts.last().map(trim_ellipsis) {
+        Some("") => (None, None),
+        Some(p) if is_domain(p) => (None, Some(p.to_owned())),
+        p => (p.map(ToOwned::to_owned), None),
+    };
+
+    ArticleHeader {
+        authors,
+        venue,
+        year,
+        publisher,
+        host,
+    }
 }
 
-fn parse_year(text: &str) -> Result<u32> {
+// "cds.cern.ch", but not "Springer Sci. Bus.".
+fn is_domain(text: &str) -> bool {
+    !text.contains(char::is_whitespace)
+        && text.contains('.')
+        && text.split('.').all(|label| !label.is_empty())
+}
+
+fn trim_ellipsis(text: &str) -> &str {
+    text.trim().trim_end_matches('…').trim_end()
+}
+
+// Venue is followed by published year, both of which may be omitted.
+fn parse_venue(text: &str) -> Option<String> {
     use regex::Regex;
 
     lazy_static! {
-        static ref RE: Regex = Regex::new(r".*\s-\s.*((18|19|20)(\d{2}))(\s-\s.+)?").unwrap();
+        static ref RE: Regex = Regex::new(r"^(.*?),?\s*((18|19|20)\d{2})?$").unwrap();
     }
 
-    let year = {
-        let caps = try_html_bad!(RE.captures(text));
-        let year = try_html_bad!(caps.get(1));
-        year.as_str().parse().unwrap()
-    };
-
-    Ok(year)
+    let caps = RE.captures(text)?;
+    let venue = trim_ellipsis(caps.get(1)?.as_str());
+    if venue.is_empty() {
+        None
+    } else {
+        Some(venue.to_owned())
+    }
 }
 
-fn scrape_article_footer(node: &Node) -> Result<ArticleFooter> {
-    // Footer format:
-    //
-    // <div class="gs_fl">
-    //   something
-    //   <a href="/scholar?cites=000000>Cited by 999</a>
-    //   something
-    // </div>
+fn parse_user_id(url: &str) -> Result<String> {
+    use regex::Regex;
 
-    let footer_nodes = {
-        let pos = Class("gs_fl");
-        try_html_bad!(node.find(pos).nth(0))
-            .children()
-            .into_selection()
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"citations\?user=([\w-]+)").unwrap();
+    }
+
+    let user_id = {
+        let caps = try_html_bad!(RE.captures(url));
+        try_html_bad!(caps.get(1)).as_str().to_owned()
     };
 
-    let citation_node = footer_nodes
-        .filter(|n: &Node| {
-            if let Some(id_url) = n.attr("href") {
-                parse_cluster_id(id_url).is_ok()
-            } else {
-                false
-            }
-        })
-        .first();
-    let citation_node = try_html_bad!(citation_node);
+    Ok(user_id)
+}
 
-    let cluster_id = {
-        let id_url = citation_node.attr("href").unwrap();
-        parse_cluster_id(id_url).unwrap()
-    };
-    let citation_count = parse_citation_count(&citation_node.text())?;
+fn parse_email_domain(text: &str, language: Language) -> Option<String> {
+    use regex::Regex;
 
-    Ok(ArticleFooter {
-        cluster_id,
-        citation_count,
-    })
+    lazy_static! {
+        static ref RES: Vec<(Language, Regex)> = Language::all()
+            .iter()
+            .map(|&lang| {
+                let words = ::regex::escape(lang.texts().email_words);
+                (lang, Regex::new(&format!(r"{}\s*([\w.-]+)", words)).unwrap())
+            })
+            .collect();
+    }
+
+    let re = &RES.iter().find(|&&(lang, _)| lang == language)?.1;
+    re.captures(text).map(|caps| caps[1].to_owned())
 }
 
-fn parse_cluster_id(url: &str) -> Result<u64> {
+fn parse_since_year(text: &str) -> Option<u32> {
     use regex::Regex;
 
     lazy_static! {
-        static ref RE: Regex = Regex::new(r"(cluster|cites)=(\d+)").unwrap();
+        static ref RE: Regex = Regex::new(r"(18|19|20)\d{2}").unwrap();
     }
 
-    let cluster_id = {
-        let caps = try_html_bad!(RE.captures(url));
-        let id = try_html_bad!(caps.get(2));
-        id.as_str().parse()?
-    };
+    RE.find(text).and_then(|m| m.as_str().parse().ok())
+}
 
-    Ok(cluster_id)
+fn parse_z_index(style: &str) -> Option<usize> {
+    use regex::Regex;
+
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"z-index:\s*(\d+)").unwrap();
+    }
+
+    RE.captures(style).and_then(|caps| caps[1].parse().ok())
 }
 
-fn parse_citation_count(text: &str) -> Result<u32> {
+fn parse_year(text: &str) -> Result<u32> {
     use regex::Regex;
 
     lazy_static! {
-        static ref RE: Regex = Regex::new(r"[^\d]+(\d+)").unwrap();
+        static ref RE: Regex = Regex::new(r".*\s-\s.*((18|19|20)(\d{2}))(\s-\s.+)?").unwrap();
     }
 
-    let count = {
+    let year = {
         let caps = try_html_bad!(RE.captures(text));
-        let count = try_html_bad!(caps.get(1));
-        count.as_str().parse().unwrap()
+        let year = try_html_bad!(caps.get(1));
+        year.as_str().parse().unwrap()
     };
 
-    Ok(count)
+    Ok(year)
 }
 
-#[cfg(test)]
-mod tests {
+fn scrape_article_snippet(node: &Node) -> Option<String> {
+    // Snippet format:
+    //
+    // <div class="gs_rs">
+    //   <span class="gs_age">3 days ago - </span>
+    //   … part of abstract <br>
+    //   broken into lines …<br>
+    // </div>
+    //
+    // 'div' may not exist.
+    // 'span' exists only in results sorted by date.
+
+    let snippet_node = {
+        let pos = Class("gs_rs");
+        node.find(pos).next()?
+    };
+
+    let text = snippet_node
+        .children()
+        .filter(|n| !n.is(Class("gs_age")))
+        .map(|n| n.text())
+        .collect::<String>();
+
+    let snippet = normalize_snippet(&text);
+    if snippet.is_empty() {
+        None
+    } else {
+        Some(snippet)
+    }
+}
+
+fn scrape_article_age(node: &Node) -> Option<u32> {
+    let age_node = {
+        let pos = Class("gs_rs").descendant(Class("gs_age"));
+        node.find(pos).next()?
+    };
+
+    parse_days_ago(&age_node.text())
+}
+
+// "3 days ago - ", "1 day ago - ", "3 日前 - ", "vor 3 Tagen - ", etc.
+fn parse_days_ago(text: &str) -> Option<u32> {
+    use regex::Regex;
+
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"(\d+)").unwrap();
+    }
+
+    let caps = RE.captures(text)?;
+    caps.get(1)?.as_str().parse().ok()
+}
+
+// Collapses whitespaces including line breaks,
+// and removes ellipses at the beginning and the end.
+fn normalize_snippet(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    collapsed
+        .trim_matches(|c: char| c == '…' || c.is_whitespace())
+        .trim_start_matches("...")
+        .trim_end_matches("...")
+        .trim()
+        .to_owned()
+}
+
+fn scrape_full_text_links(node: &Node, language: Language) -> Vec<FullTextLink> {
+    // Full text links are placed besides the article:
+    //
+    // <div class="gs_r">
+    //   <div class="gs_ggs">
+    //     <div class="gs_ggsd">
+    //       <div class="gs_or_ggsm">
+    //         <a href="http://paper.pdf"><span>[PDF]</span> example.com</a>
+    //       </div>
+    //     </div>
+    //   </div>
+    //   <div class="gs_ri">
+    //     article
+    //   </div>
+    // </div>
+    //
+    // 'gs_ggs' may not exist, and 'span' may not exist either.
+
+    let parent = match node.parent() {
+        Some(p) => p,
+        None => return vec![],
+    };
+
+    let pos = Class("gs_or_ggsm").child(Name("a"));
+    parent
+        .find(pos)
+        .filter_map(|n| {
+            let url = n.attr("href")?.to_owned();
+            let kind = n.find(Name("span"))
+                .next()
+                .and_then(|s| parse_paper_kind(&s.text(), language).ok());
+            let source = n.children()
+                .filter(|c| c.name() != Some("span"))
+                .map(|c| c.text())
+                .collect::<String>()
+                .trim()
+                .to_owned();
+
+            Some(FullTextLink { url, source, kind })
+        })
+        .collect()
+}
+
+fn scrape_article_footer(node: &Node) -> Result<ArticleFooter> {
+    // Footer format:
+    //
+    // <div class="gs_fl">
+    //   something
+    //   <a href="/scholar?cites=000000>Cited by 999</a>
+    //   <a href="/scholar?q=related:AAAAAAAAAAAJ:scholar.google.com/">Related articles</a>
+    //   <a href="/scholar?cluster=000000">All 9 versions</a>
+    //   something
+    // </div>
+    //
+    // 'Cited by' link does not exist if the paper is not cited at all.
+    // 'All versions' link does not exist if the paper has only one version.
+
+    let footer_links = {
+        let pos = Class("gs_fl");
+        try_html_bad!(node.find(pos).nth(0))
+            .children()
+            .into_selection()
+            .filter(Name("a"))
+    };
+
+    let citation_node = footer_links.iter().find(|n: &Node| {
+        if let Some(url) = n.attr("href") {
+            url.contains("cites=")
+        } else {
+            false
+        }
+    });
+
+    let cluster_id = {
+        let from_cluster_url = footer_links
+            .iter()
+            .filter_map(|n| n.attr("href"))
+            .filter_map(|u| parse_cluster_id(u).ok())
+            .next();
+        let from_related_url = || {
+            footer_links
+                .iter()
+                .filter_map(|n| n.attr("href"))
+                .filter_map(|u| parse_related_cluster_id(u).ok())
+                .next()
+        };
+        try_html_bad!(from_cluster_url.or_else(from_related_url))
+    };
+
+    let citation_count = match citation_node {
+        Some(n) => parse_citation_count(&n.text())?,
+        None => 0,
+    };
+
+    let versions_node = footer_links.iter().find(|n: &Node| {
+        if let Some(url) = n.attr("href") {
+            url.contains("cluster=")
+        } else {
+            false
+        }
+    });
+    let version_count = match versions_node {
+        Some(n) => Some(parse_version_count(&n.text())?),
+        None => None,
+    };
+
+    Ok(ArticleFooter {
+        cluster_id,
+        citation_count,
+        version_count,
+    })
+}
+
+fn parse_cluster_id(url: &str) -> Result<u64> {
+    use regex::Regex;
+
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"(cluster|cites)=(\d+)").unwrap();
+    }
+
+    let cluster_id = {
+        let caps = try_html_bad!(RE.captures(url));
+        let id = try_html_bad!(caps.get(2));
+        id.as_str().parse()?
+    };
+
+    Ok(cluster_id)
+}
+
+// 'Related articles' URL contains the cluster ID encoded as `related_id` does.
+fn parse_related_cluster_id(url: &str) -> Result<u64> {
+    use regex::Regex;
+
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"related:([A-Za-z0-9_-]+):").unwrap();
+    }
+
+    let encoded = {
+        let caps = try_html_bad!(RE.captures(url));
+        try_html_bad!(caps.get(1)).as_str()
+    };
+
+    Ok(try_html_bad!(related_id::decode(encoded)))
+}
+
+// "Cited by 1,234", "Zitiert von: 1.234", "引用元 1234", etc.
+fn parse_citation_count(text: &str) -> Result<u32> {
+    use regex::Regex;
+
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"[^\d]+(\d[\d,.\s]*)").unwrap();
+    }
+
+    let count = {
+        let caps = try_html_bad!(RE.captures(text));
+        let count = try_html_bad!(caps.get(1));
+        parse_grouped_number(count.as_str())?
+    };
+
+    Ok(try_html_bad!(u32::try_from(count).ok()))
+}
+
+// "All 1,234 versions", "Alle 1.234 Versionen", "全 1234 バージョン", etc.
+fn parse_version_count(text: &str) -> Result<u32> {
+    use regex::Regex;
+
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"(\d[\d,.\s]*)").unwrap();
+    }
+
+    let count = {
+        let caps = try_html_bad!(RE.captures(text));
+        let count = try_html_bad!(caps.get(1));
+        parse_grouped_number(count.as_str())?
+    };
+
+    Ok(try_html_bad!(u32::try_from(count).ok()))
+}
+
+// "About 1,960,000 results (0.32 sec)", "Ungefähr 1.960.000 Ergebnisse (0,32 Sek.)",
+// "約 1,960,000 件 (0.32 秒)", "Page 2 of about 1,960,000 results (0.32 sec)", etc.
+fn parse_result_count(text: &str, language: Language) -> Option<u64> {
+    use regex::Regex;
+
+    lazy_static! {
+        static ref RES: Vec<(Language, Regex)> = Language::all()
+            .iter()
+            .map(|&lang| {
+                let words = lang.texts()
+                    .result_words
+                    .iter()
+                    .map(|w| ::regex::escape(w))
+                    .collect::<Vec<_>>()
+                    .join("|");
+                let re = Regex::new(&format!(r"(\d[\d,.\s]*?)\s*(?:{})", words)).unwrap();
+                (lang, re)
+            })
+            .collect();
+    }
+
+    let re = &RES.iter().find(|&&(lang, _)| lang == language)?.1;
+    let caps = re.captures(text)?;
+    parse_grouped_number(caps.get(1)?.as_str()).ok()
+}
+
+// Digits grouped by locale-dependent separators: "1,234", "1.234", "1 234".
+fn parse_grouped_number(text: &str) -> Result<u64> {
+    let digits = text.chars()
+        .filter(|c| c.is_ascii_digit())
+        .collect::<String>();
+    Ok(digits.parse()?)
+}
+
+#[cfg(test)]
+mod tests {
     use super::*;
 
+    fn set_versions(paper: &mut Paper, count: u32) {
+        paper.version_count = Some(count);
+        paper.versions_url = Some(format!(
+            "https://scholar.google.com/scholar?cluster={}",
+            paper.cluster_id
+        ));
+    }
+
+    fn author(name: &str, user_id: Option<&str>) -> Author {
+        Author {
+            name: name.to_owned(),
+            user_id: user_id.map(ToOwned::to_owned),
+        }
+    }
+
     #[test]
     fn parse_year_pass() {
         assert_eq!(parse_year("foo - journal, 2000 - bar").unwrap(), 2000);
@@ -390,6 +1324,70 @@ mod tests {
         assert!(parse_year("- 1800").is_err());
     }
 
+    #[test]
+    fn parse_paper_kind_pass() {
+        assert_eq!(parse_paper_kind("[BOOK][B]", Language::English).unwrap(), PaperKind::Book);
+        assert_eq!(parse_paper_kind("[CITATION][C]", Language::English).unwrap(), PaperKind::Citation);
+        assert_eq!(parse_paper_kind("[HTML]", Language::English).unwrap(), PaperKind::Html);
+        assert_eq!(parse_paper_kind("[PDF]", Language::English).unwrap(), PaperKind::Pdf);
+        assert_eq!(
+            parse_paper_kind("[DOC]", Language::English).unwrap(),
+            PaperKind::Other(String::from("DOC"))
+        );
+
+        assert_eq!(parse_paper_kind("[書籍][B]", Language::Japanese).unwrap(), PaperKind::Book);
+        assert_eq!(parse_paper_kind("[引用][C]", Language::Japanese).unwrap(), PaperKind::Citation);
+        assert_eq!(parse_paper_kind("[BUCH][B]", Language::German).unwrap(), PaperKind::Book);
+        assert_eq!(parse_paper_kind("[ZITATION][C]", Language::German).unwrap(), PaperKind::Citation);
+
+        // Tags of other languages are not recognized.
+        assert_eq!(
+            parse_paper_kind("[BUCH][B]", Language::English).unwrap(),
+            PaperKind::Other(String::from("BUCH"))
+        );
+    }
+
+    #[test]
+    fn parse_paper_kind_fail() {
+        assert!(parse_paper_kind("foo", Language::English).is_err());
+        assert!(parse_paper_kind("", Language::English).is_err());
+    }
+
+    #[test]
+    fn parse_venue_test() {
+        assert_eq!(
+            parse_venue("Physical Review, 1959"),
+            Some(String::from("Physical Review"))
+        );
+        assert_eq!(
+            parse_venue("Proceedings of the Royal Society of …, 1928"),
+            Some(String::from("Proceedings of the Royal Society of"))
+        );
+        assert_eq!(parse_venue("Nature"), Some(String::from("Nature")));
+        assert_eq!(parse_venue("1996"), None);
+        assert_eq!(parse_venue(""), None);
+    }
+
+    #[test]
+    fn parse_user_id_test() {
+        assert_eq!(
+            parse_user_id("https://scholar.google.co.jp/citations?user=89qN_8gAAAAJ&hl=en")
+                .unwrap(),
+            "89qN_8gAAAAJ"
+        );
+        assert!(parse_user_id("https://scholar.google.co.jp/scholar?cluster=0").is_err());
+    }
+
+    #[test]
+    fn normalize_snippet_test() {
+        assert_eq!(
+            normalize_snippet("  … foo bar <br>\n  baz, … qux …  "),
+            "foo bar <br> baz, … qux"
+        );
+        assert_eq!(normalize_snippet("... foo\u{a0}bar..."), "foo bar");
+        assert_eq!(normalize_snippet(" … "), "");
+    }
+
     #[test]
     fn parse_cluster_id_pass() {
         assert_eq!(parse_cluster_id("cluster=123456").unwrap(), 123456);
@@ -407,15 +1405,90 @@ mod tests {
         assert!(parse_cluster_id("cluster=aaaaaa").is_err());
     }
 
+    #[test]
+    fn parse_related_cluster_id_pass() {
+        assert_eq!(
+            parse_related_cluster_id(
+                "https://scholar.google.co.jp/scholar?q=related:n-S3Szmx-uQJ:scholar.google.com/"
+            ).unwrap(),
+            16499695044466828447
+        );
+        assert_eq!(
+            parse_related_cluster_id("scholar?q=related:KAyT5n6OsHYJ:scholar.google.com/")
+                .unwrap(),
+            8552492368061991976
+        );
+    }
+
+    #[test]
+    fn parse_related_cluster_id_fail() {
+        assert!(parse_related_cluster_id("foo").is_err());
+        assert!(parse_related_cluster_id("related:n-S3Szmx:scholar.google.com/").is_err());
+    }
+
     #[test]
     fn parse_citation_count_pass() {
         assert_eq!(parse_citation_count("Cited by 111").unwrap(), 111);
         assert_eq!(parse_citation_count("引用元 222").unwrap(), 222);
+        assert_eq!(parse_citation_count("Zitiert von: 333").unwrap(), 333);
+        assert_eq!(parse_citation_count("Cited by 1,234").unwrap(), 1234);
+        assert_eq!(parse_citation_count("Zitiert von: 1.234").unwrap(), 1234);
+        assert_eq!(
+            parse_citation_count("Cited by 4,294,967,295").unwrap(),
+            4294967295
+        );
+    }
+
+    #[test]
+    fn parse_version_count_pass() {
+        assert_eq!(parse_version_count("All 3 versions").unwrap(), 3);
+        assert_eq!(parse_version_count("全 12 バージョン").unwrap(), 12);
+        assert_eq!(parse_version_count("Alle 21 Versionen").unwrap(), 21);
+    }
+
+    #[test]
+    fn parse_result_count_test() {
+        assert_eq!(
+            parse_result_count("About 1,960,000 results (0.32 sec)", Language::English),
+            Some(1960000)
+        );
+        assert_eq!(parse_result_count("1 result (0.01 sec)", Language::English), Some(1));
+        assert_eq!(
+            parse_result_count("Page 2 of about 6,961 results (0.13 sec)", Language::English),
+            Some(6961)
+        );
+        assert_eq!(parse_result_count("約 1,960,000 件 （0.32 秒）", Language::Japanese), Some(1960000));
+        assert_eq!(
+            parse_result_count("Ungefähr 1.960.000 Ergebnisse (0,32 Sek.)", Language::German),
+            Some(1960000)
+        );
+        assert_eq!(
+            parse_result_count("Seite 2 von ungefähr 6.961 Ergebnissen (0,13 Sek.)", Language::German),
+            Some(6961)
+        );
+        assert_eq!(parse_result_count("foo", Language::English), None);
+        assert_eq!(parse_result_count("約 1,960,000 件 （0.32 秒）", Language::German), None);
+    }
+
+    #[test]
+    fn parse_days_ago_test() {
+        assert_eq!(parse_days_ago("1 day ago - "), Some(1));
+        assert_eq!(parse_days_ago("14 days ago - "), Some(14));
+        assert_eq!(parse_days_ago("3 日前 - "), Some(3));
+        assert_eq!(parse_days_ago("vor 3 Tagen - "), Some(3));
+        assert_eq!(parse_days_ago("yesterday"), None);
+    }
+
+    #[test]
+    fn parse_version_count_fail() {
+        assert!(parse_version_count("foo").is_err());
+        assert!(parse_version_count("All 4,294,967,296 versions").is_err());
     }
 
     #[test]
     fn parse_citation_count_fail() {
         assert!(parse_citation_count("foo").is_err());
+        assert!(parse_citation_count("Cited by 4,294,967,296").is_err());
     }
 
     #[test]
@@ -434,6 +1507,19 @@ mod tests {
         };
 
         assert!(!unblocked_doc.is_blocked());
+
+        let blocked_ja_doc = SearchDocument::from(
+            "<div><div>お使いのコンピュータ ネットワークから通常と異なるトラフィックが\
+             検出されました。</div></div>",
+        );
+        assert!(!blocked_ja_doc.is_blocked());
+        assert!(blocked_ja_doc.with_language(Language::Japanese).is_blocked());
+
+        let blocked_de_doc = SearchDocument::from(
+            "<div><div>Unsere Systeme haben ungewöhnlichen Datenverkehr aus Ihrem \
+             Computernetzwerk festgestellt.</div></div>",
+        ).with_language(Language::German);
+        assert!(blocked_de_doc.is_blocked());
     }
 
     #[test]
@@ -454,15 +1540,29 @@ mod tests {
                 16499695044466828447,
             );
             paper.link = Some(String::from("http://cds.cern.ch/record/2280881"));
+            paper.kind = Some(PaperKind::Book);
+            paper.authors = vec![author("J Zinn-Justin", Some("lpTL7HwAAAAJ"))];
             paper.year = Some(1996);
+            paper.host = Some(String::from("cds.cern.ch"));
+            paper.snippet = Some(String::from(
+                "Abstract Over the last twenty years quantum field theory has become not only \
+                 the framework for the discussion of all fundamental interactions except gravity, \
+                 but also for the understanding of second-order phase transitions \
+                 in statistical mechanics. This advanced text",
+            ));
             paper.citation_count = Some(4821);
+            set_versions(&mut paper, 3);
             paper
         });
 
         assert_eq!(papers[1], {
             let mut paper = Paper::new("Quantum theory of solids", 8552492368061991976);
+            paper.kind = Some(PaperKind::Citation);
+            paper.authors = vec![author("C Kittel", None), author("C Fong", None)];
             paper.year = Some(1963);
+            paper.publisher = Some(String::from("Wiley New York"));
             paper.citation_count = Some(4190);
+            set_versions(&mut paper, 3);
             paper
         });
 
@@ -474,12 +1574,128 @@ mod tests {
             paper.link = Some(String::from(
                 "https://journals.aps.org/pr/abstract/10.1103/PhysRev.115.485",
             ));
+            paper.authors = vec![author("Y Aharonov", None), author("D Bohm", None)];
+            paper.venue = Some(String::from("Physical Review"));
             paper.year = Some(1959);
+            paper.publisher = Some(String::from("APS"));
+            paper.full_text_links = vec![FullTextLink {
+                url: String::from("http://link.aps.org/pdf/10.1103/PhysRev.115.485"),
+                source: String::from("aps.org"),
+                kind: Some(PaperKind::Pdf),
+            }];
+            paper.snippet = Some(String::from(
+                "Abstract In this paper, we discuss some interesting properties of \
+                 the electromagnetic potentials in the quantum domain. We shall show that, \
+                 contrary to the conclusions of classical mechanics, there exist effects of \
+                 potentials on charged particles, even in the region",
+            ));
             paper.citation_count = Some(6961);
+            set_versions(&mut paper, 21);
             paper
         });
     }
 
+    #[test]
+    fn search_document_scrape_localized_test() {
+        use std::fs;
+
+        let scrape = |path, language| {
+            let file = fs::File::open(path).unwrap();
+            let doc = SearchDocument::from_read(file)
+                .unwrap()
+                .with_language(language);
+            assert!(!doc.is_blocked());
+            (doc.scrape_papers().unwrap(), doc.scrape_result_count())
+        };
+
+        let (papers, result_count) = scrape("src/test_html/quantum_theory.html", Language::English);
+        assert_eq!(result_count, Some(1960000));
+
+        for &(path, language) in &[
+            ("src/test_html/quantum_theory_ja.html", Language::Japanese),
+            ("src/test_html/quantum_theory_de.html", Language::German),
+        ] {
+            assert_eq!(scrape(path, language), (papers.clone(), result_count));
+        }
+    }
+
+    #[test]
+    fn search_document_scrape_uncited_test() {
+        use std::fs;
+
+        let papers = {
+            let file = fs::File::open("src/test_html/uncited.html").unwrap();
+            let doc = SearchDocument::from_read(file).unwrap();
+            doc.scrape_papers().unwrap()
+        };
+
+        assert_eq!(papers.len(), 3);
+
+        assert_eq!(papers[0], {
+            let mut paper = Paper::new("Quantum theory lecture notes", 11845712235651853713);
+            paper.link = Some(String::from("http://example.ac.jp/lecture/quantum.pdf"));
+            paper.kind = Some(PaperKind::Pdf);
+            paper.authors = vec![author("T Tanaka", None)];
+            paper.year = Some(2015);
+            paper.host = Some(String::from("example.ac.jp"));
+            paper.snippet = Some(String::from(
+                "These lecture notes cover the basics of quantum theory … \
+                 for undergraduate students",
+            ));
+            paper.citation_count = Some(12);
+            set_versions(&mut paper, 2);
+            paper
+        });
+
+        // 'Cited by' link is missing; cluster ID is taken from 'All versions' link
+        assert_eq!(papers[1], {
+            let mut paper = Paper::new(
+                "Notes on quantum theory of angular momentum",
+                4401617830405236058,
+            );
+            paper.link = Some(String::from("http://example.edu/notes/qm2.html"));
+            paper.authors = vec![author("K Suzuki", None)];
+            paper.year = Some(2017);
+            paper.host = Some(String::from("example.edu"));
+            paper.citation_count = Some(0);
+            set_versions(&mut paper, 2);
+            paper
+        });
+
+        // Only 'Related articles' link exists
+        assert_eq!(papers[2], {
+            let mut paper = Paper::new(
+                "Introductory lecture notes on quantum theory",
+                9003587423478211147,
+            );
+            paper.kind = Some(PaperKind::Citation);
+            paper.authors = vec![author("H Sato", None)];
+            paper.year = Some(2016);
+            paper.citation_count = Some(0);
+            paper
+        });
+    }
+
+    #[test]
+    fn search_document_scrape_base_url_test() {
+        use std::fs;
+
+        let base = BaseUrl::new("http://localhost:8080/").unwrap();
+        let papers = {
+            let file = fs::File::open("src/test_html/quantum_theory.html").unwrap();
+            let doc = SearchDocument::from_read(file).unwrap().with_base_url(&base);
+            doc.scrape_papers().unwrap()
+        };
+
+        for paper in &papers {
+            assert!(paper.citation_url.starts_with("http://localhost:8080/scholar?cites="));
+            if let Some(ref url) = paper.versions_url {
+                assert!(url.starts_with("http://localhost:8080/scholar?cluster="));
+            }
+        }
+        assert!(papers.iter().any(|p| p.versions_url.is_some()));
+    }
+
     #[test]
     fn citation_document_scrape_test() {
         use std::fs;
@@ -510,8 +1726,23 @@ mod tests {
             paper.link = Some(String::from(
                 "http://rspa.royalsocietypublishing.org/content/royprsa/392/1802/45.full.pdf",
             ));
+            paper.authors = vec![author("MV Berry", Some("89qN_8gAAAAJ"))];
+            paper.venue = Some(String::from("Proceedings of the Royal Society of"));
             paper.year = Some(1984);
+            paper.host = Some(String::from("rspa.royalsocietypublishing.org"));
+            paper.full_text_links = vec![FullTextLink {
+                url: String::from("http://www.academia.edu/download/34752305/berr83.pdf"),
+                source: String::from("academia.edu"),
+                kind: Some(PaperKind::Pdf),
+            }];
+            paper.snippet = Some(String::from(
+                "Abstract A quantal system in an eigenstate, slowly transported round \
+                 a circuit C by varying parameters $\\mathbf {R} $ in its Hamiltonian \
+                 $\\hat {H}(\\mathrm {R}) $, will acquire a geometrical phase factor \
+                 $\\exp {i\\gamma (\\mathrm {C})} $ in addition to the familiar",
+            ));
             paper.citation_count = Some(7813);
+            set_versions(&mut paper, 9);
             paper
         });
 
@@ -523,8 +1754,23 @@ mod tests {
             paper.link = Some(String::from(
                 "https://www.nature.com/nmat/journal/v6/n1/abs/nmat1804.html",
             ));
+            paper.authors = vec![author("SW Cheong", None), author("M Mostovoy", None)];
+            paper.venue = Some(String::from("Nature materials"));
             paper.year = Some(2007);
+            paper.host = Some(String::from("nature.com"));
+            paper.full_text_links = vec![FullTextLink {
+                url: String::from("https://pure.rug.nl/ws/files/6702041/2007NatureMaterCheong.pdf"),
+                source: String::from("rug.nl"),
+                kind: Some(PaperKind::Pdf),
+            }];
+            paper.snippet = Some(String::from(
+                "Abstract Magnetism and ferroelectricity are essential to many forms of \
+                 current technology, and the quest for multiferroic materials, where these two \
+                 phenomena are intimately coupled, is of great technological and fundamental \
+                 importance. Ferroelectricity and magnetism tend",
+            ));
             paper.citation_count = Some(3232);
+            set_versions(&mut paper, 16);
             paper
         });
 
@@ -535,12 +1781,64 @@ mod tests {
                  hl=en&lr=&id=nnuW_kVJ500C&oi=fnd&pg=PR17\
                  &ots=vrupeDXT-V&sig=MofOsrk4Hh9qXjkS_WuQ7jHr2sY",
             ));
+            paper.kind = Some(PaperKind::Book);
+            paper.authors = vec![author("LH Ryder", None)];
             paper.year = Some(1996);
+            paper.host = Some(String::from("books.google.com"));
+            paper.snippet = Some(String::from(
+                "This book is a modern introduction to the ideas and techniques of \
+                 quantum field theory. After a brief overview of particle physics and a survey \
+                 of relativistic wave equations and Lagrangian methods, the author develops \
+                 the quantum theory of scalar and spinor fields,",
+            ));
             paper.citation_count = Some(2911);
+            set_versions(&mut paper, 11);
             paper
         });
     }
 
+    #[test]
+    fn citation_document_scrape_sorted_by_date_test() {
+        use std::fs;
+
+        let scrape = |path| {
+            let file = fs::File::open(path).unwrap();
+            let doc = CitationDocument::from_read(file).unwrap();
+            doc.scrape_papers().unwrap()
+        };
+
+        let papers = scrape("src/test_html/quantum_theory_citations.html");
+        let date_papers = scrape("src/test_html/quantum_theory_citations_date.html");
+
+        assert!(papers.iter().all(|p| p.days_ago.is_none()));
+        assert_eq!(
+            date_papers.iter().map(|p| p.days_ago).collect::<Vec<_>>(),
+            vec![
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(4),
+                Some(5),
+                Some(7),
+                Some(9),
+                None,
+                Some(12),
+                Some(14),
+            ]
+        );
+
+        // The age is not a part of the snippet.
+        for (paper, date_paper) in papers.into_iter().zip(date_papers) {
+            assert_eq!(
+                Paper {
+                    days_ago: None,
+                    ..date_paper
+                },
+                paper
+            );
+        }
+    }
+
     #[test]
     fn cluster_document_scrape_test() {
         use std::fs;
@@ -557,10 +1855,324 @@ mod tests {
                 "Quantum field theory and critical phenomena",
                 6453279145216378381,
             );
+            paper.authors = vec![author("J Zinn-Justin", Some("lpTL7HwAAAAJ"))];
             paper.year = Some(1996);
+            paper.host = Some(String::from("cds.cern.ch"));
+            paper.snippet = Some(String::from(
+                "Abstract Over the last twenty years quantum field theory has become not only \
+                 the framework for the discussion of all fundamental interactions except gravity, \
+                 but also for the understanding of second-order phase transitions \
+                 in statistical mechanics. This advanced text",
+            ));
             paper.link = Some(String::from("http://cds.cern.ch/record/2280881"));
+            paper.kind = Some(PaperKind::Book);
             paper.citation_count = Some(4932);
             paper
         });
     }
+
+    #[test]
+    fn versions_document_scrape_test() {
+        use std::fs;
+
+        let versions = {
+            let file = fs::File::open("src/test_html/quantum_theory_cluster.html").unwrap();
+            let doc = VersionsDocument::from_read(file).unwrap();
+            doc.scrape_versions().unwrap()
+        };
+
+        assert_eq!(versions.len(), 6);
+
+        assert_eq!(
+            versions[0],
+            Version {
+                title: String::from("Quantum field theory and critical phenomena"),
+                link: Some(String::from("http://cds.cern.ch/record/2280881")),
+                kind: Some(PaperKind::Book),
+                venue: None,
+                year: Some(1996),
+                publisher: None,
+                host: Some(String::from("cds.cern.ch")),
+            }
+        );
+
+        assert_eq!(
+            versions[1],
+            Version {
+                title: String::from("Quantum field theory and critical phenomena"),
+                link: Some(String::from("http://inspirehep.net/record/588675/")),
+                kind: Some(PaperKind::Citation),
+                venue: Some(String::from("Int. Ser. Monogr. Phys.")),
+                year: Some(2002),
+                publisher: None,
+                host: Some(String::from("inspirehep.net")),
+            }
+        );
+    }
+
+    #[test]
+    fn scrape_bibtex_test() {
+        let entry = scrape_bibtex(
+            42,
+            r#"@Article{foo2000bar,
+  title={On {B}ar and {\"U}ber},
+  author = "Foo, Alice and Baz, Bob",
+  year=2000,
+}"#,
+        ).unwrap();
+
+        assert_eq!(entry.entry_type, "article");
+        assert_eq!(entry.key, "foo2000bar");
+        assert_eq!(entry.get("title"), Some(r#"On {B}ar and {\"U}ber"#));
+        assert_eq!(entry.get("author"), Some("Foo, Alice and Baz, Bob"));
+        assert_eq!(entry.get("year"), Some("2000"));
+        assert_eq!(entry.get("journal"), None);
+
+        assert!(scrape_bibtex(42, "").is_err());
+        assert!(scrape_bibtex(42, "@book{foo, title={bar}").is_err());
+        assert!(scrape_bibtex(42, "@book{foo, title={bar").is_err());
+    }
+
+    #[test]
+    fn cite_document_scrape_test() {
+        use std::fs;
+
+        let links = {
+            let file = fs::File::open("src/test_html/quantum_theory_cite.html").unwrap();
+            let doc = CiteDocument::from_read(file).unwrap();
+            doc.scrape_export_links().unwrap()
+        };
+
+        let export_url = |ext: &str, scisf: u32| {
+            format!(
+                "https://scholar.googleusercontent.com/scholar.{}?\
+                 q=info:n-S3Szmx-uQJ:scholar.google.com/&output=citation&\
+                 scisig=AAGBfm0AAAAAWqPRxJ2pZ4vD8VbMqX6jY3kLr1tN0sQe&scisf={}&\
+                 ct=citation&cd=-1&hl=en",
+                ext, scisf
+            )
+        };
+
+        assert_eq!(
+            links,
+            ExportLinks {
+                bibtex: Some(export_url("bib", 4)),
+                endnote: Some(export_url("enw", 3)),
+                refman: Some(export_url("ris", 2)),
+                refworks: Some(export_url("rfw", 1)),
+            }
+        );
+
+        let doc = CiteDocument::from("<html></html>");
+        assert!(doc.scrape_export_links().is_err());
+    }
+
+    #[test]
+    fn bibtex_scrape_test() {
+        use std::fs;
+        use std::io::Read;
+
+        let bib = {
+            let mut file = fs::File::open("src/test_html/quantum_theory.bib").unwrap();
+            let mut s = String::new();
+            file.read_to_string(&mut s).unwrap();
+            s
+        };
+
+        let entry = scrape_bibtex(16499695044466828447, &bib).unwrap();
+        assert_eq!(
+            entry,
+            BibtexEntry {
+                cluster_id: 16499695044466828447,
+                entry_type: String::from("book"),
+                key: String::from("zinn1996quantum"),
+                fields: vec![
+                    (
+                        String::from("title"),
+                        String::from("Quantum field theory and critical phenomena"),
+                    ),
+                    (String::from("author"), String::from("Zinn-Justin, Jean")),
+                    (String::from("number"), String::from("SACLAY-SPHT-T--88-102")),
+                    (String::from("year"), String::from("1996")),
+                    (String::from("publisher"), String::from("Clarendon Press")),
+                ],
+            }
+        );
+
+        assert_eq!(entry.to_string(), bib.trim());
+    }
+
+    #[test]
+    fn is_domain_test() {
+        assert!(is_domain("cds.cern.ch"));
+        assert!(is_domain("books.google.com"));
+        assert!(!is_domain("APS"));
+        assert!(!is_domain("Wiley New York"));
+        assert!(!is_domain("Springer Sci. Bus."));
+        assert!(!is_domain("Phys."));
+    }
+
+    #[test]
+    fn parse_email_domain_test() {
+        assert_eq!(
+            parse_email_domain("Verified email at cea.fr - Homepage", Language::English),
+            Some(String::from("cea.fr"))
+        );
+        assert_eq!(
+            parse_email_domain("Verified email at cs.example.edu", Language::English),
+            Some(String::from("cs.example.edu"))
+        );
+        assert_eq!(
+            parse_email_domain("確認したメール アドレス: cea.fr - ホームページ", Language::Japanese),
+            Some(String::from("cea.fr"))
+        );
+        assert_eq!(
+            parse_email_domain("Bestätigte E-Mail-Adresse bei cea.fr - Startseite", Language::German),
+            Some(String::from("cea.fr"))
+        );
+        assert_eq!(parse_email_domain("No verified email", Language::English), None);
+        assert_eq!(
+            parse_email_domain("Verified email at cea.fr - Homepage", Language::German),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_z_index_test() {
+        assert_eq!(parse_z_index("right:16px;height:66px;z-index:1"), Some(1));
+        assert_eq!(parse_z_index("z-index: 12"), Some(12));
+        assert_eq!(parse_z_index("right:16px"), None);
+    }
+
+    #[test]
+    fn author_profile_document_scrape_test() {
+        use std::fs;
+
+        let profile = {
+            let file = fs::File::open("src/test_html/author_profile.html").unwrap();
+            let doc = AuthorProfileDocument::from_read(file).unwrap();
+            doc.scrape_profile().unwrap()
+        };
+
+        assert_eq!(profile.name, "Jean Zinn-Justin");
+        assert_eq!(
+            profile.affiliation,
+            Some(String::from("IRFU/CEA, Paris-Saclay University"))
+        );
+        assert_eq!(profile.email_domain, Some(String::from("cea.fr")));
+        assert_eq!(
+            profile.interests,
+            vec![
+                "Quantum field theory",
+                "Critical phenomena",
+                "Renormalization group",
+            ]
+        );
+
+        assert_eq!(
+            profile.metrics,
+            Some(CitationMetrics {
+                since_year: Some(2013),
+                citations: MetricValues {
+                    all: 26853,
+                    recent: 6432,
+                },
+                h_index: MetricValues { all: 58, recent: 28 },
+                i10_index: MetricValues {
+                    all: 176,
+                    recent: 70,
+                },
+            })
+        );
+
+        assert_eq!(
+            profile.citation_histogram,
+            vec![(2011, 1187), (2012, 1142), (2013, 0), (2014, 1060), (2015, 1023), (2016, 981)]
+                .into_iter()
+                .map(|(year, count)| YearlyCitations { year, count })
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(profile.publications.len(), 3);
+
+        assert_eq!(
+            profile.publications[0],
+            Publication {
+                title: String::from("Quantum field theory and critical phenomena"),
+                link: Some(String::from(
+                    "https://scholar.google.com/citations?view_op=view_citation&hl=en&\
+                     user=lpTL7HwAAAAJ&citation_for_view=lpTL7HwAAAAJ:u5HHmVD_uO8C",
+                )),
+                authors: Some(String::from("J Zinn-Justin")),
+                venue: Some(String::from("Clarendon Press")),
+                year: Some(1996),
+                cluster_id: Some(16499695044466828447),
+                citation_count: 4821,
+            }
+        );
+
+        assert_eq!(
+            profile.publications[1].venue,
+            Some(String::from("Physics Reports 344 (4), 159-178"))
+        );
+        assert_eq!(profile.publications[1].cluster_id, Some(5372419006512446453));
+
+        assert_eq!(
+            profile.publications[2],
+            Publication {
+                title: String::from("Lecture notes on renormalization"),
+                link: Some(String::from(
+                    "https://scholar.google.com/citations?view_op=view_citation&hl=en&\
+                     user=lpTL7HwAAAAJ&citation_for_view=lpTL7HwAAAAJ:9yKSN-GCB0IC",
+                )),
+                authors: Some(String::from("J Zinn-Justin, E Brezin")),
+                venue: None,
+                year: None,
+                cluster_id: None,
+                citation_count: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn author_search_document_scrape_test() {
+        use std::fs;
+
+        let authors = {
+            let file = fs::File::open("src/test_html/author_search.html").unwrap();
+            let doc = AuthorSearchDocument::from_read(file).unwrap();
+            doc.scrape_authors().unwrap()
+        };
+
+        assert_eq!(authors.len(), 3);
+
+        assert_eq!(
+            authors[0],
+            AuthorSummary {
+                user_id: String::from("lpTL7HwAAAAJ"),
+                name: String::from("Jean Zinn-Justin"),
+                affiliation: Some(String::from("IRFU/CEA, Paris-Saclay University")),
+                citation_count: Some(26853),
+                interests: vec![
+                    String::from("Quantum field theory"),
+                    String::from("Critical phenomena"),
+                    String::from("Renormalization group"),
+                ],
+            }
+        );
+
+        assert_eq!(authors[1].user_id, "Xq3-9vQAAAAJ");
+        assert_eq!(authors[1].citation_count, Some(2473));
+
+        assert_eq!(
+            authors[2],
+            AuthorSummary {
+                user_id: String::from("a1B2c3D4AAAJ"),
+                name: String::from("M Zinn"),
+                affiliation: None,
+                citation_count: None,
+                interests: vec![],
+            }
+        );
+    }
 }
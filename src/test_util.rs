@@ -0,0 +1,53 @@
+//! Helpers shared by tests of the library and the binary.
+
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use client::{ClientConfig, ScholarClient};
+use rate_limit::RateLimit;
+use retry::RetryPolicy;
+use transport::MemoryTransport;
+
+/// Client sending requests through `transport` without rate limiting.
+pub fn test_client(transport: Arc<MemoryTransport>, retry: RetryPolicy) -> ScholarClient {
+    ScholarClient::with_transport(
+        ClientConfig {
+            rate_limit: RateLimit::unlimited(),
+            retry,
+            ..ClientConfig::default()
+        },
+        transport,
+    )
+}
+
+/// Directory under the temporary directory, unique to `name` and the process.
+/// The directory is not created, but removed with its contents when dropped.
+pub struct TestDir(PathBuf);
+
+impl TestDir {
+    pub fn new(name: &str) -> Self {
+        let dir = ::std::env::temp_dir().join(format!(
+            "scholar-test-{}-{}",
+            ::std::process::id(),
+            name
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        TestDir(dir)
+    }
+}
+
+impl Deref for TestDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,280 @@
+//! `Transport` trait fetching pages for `ScholarClient`, and its implementations.
+
+use std::cmp;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use reqwest::{self, header, Url};
+
+use client::ClientConfig;
+use errors::*;
+
+/// Response of an HTTP GET request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    /// HTTP status code, like 200.
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    /// Response of status 200 with `body`.
+    pub fn ok(body: &str) -> Self {
+        Self {
+            status: 200,
+            body: body.to_owned(),
+        }
+    }
+}
+
+/// Backend sending HTTP GET requests on behalf of `ScholarClient`.
+pub trait Transport: Send + Sync {
+    /// Send a GET request to `url` and get the response, whatever its status is.
+    ///
+    /// # Return value
+    ///
+    /// `Err` only if no response is received, e.g. on network errors or timeouts.
+    fn get(&self, url: &Url) -> Result<Response>;
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn get(&self, url: &Url) -> Result<Response> {
+        (**self).get(url)
+    }
+}
+
+/// `Transport` sending requests over network with `reqwest`,
+/// keeping cookies set by Google Scholar for the following requests.
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+    cookies: Mutex<CookieJar>,
+}
+
+impl ReqwestTransport {
+    /// Create new `ReqwestTransport` with User-Agent, timeout and headers of `config`.
+    pub fn new(config: &ClientConfig) -> Result<Self> {
+        let mut headers = config.headers.clone();
+        headers.set(header::UserAgent::new(config.user_agent.clone()));
+
+        let client = {
+            let mut builder = reqwest::Client::builder();
+            builder.default_headers(headers).timeout(config.timeout);
+            builder.build()?
+        };
+
+        Ok(Self {
+            client,
+            cookies: Mutex::new(CookieJar::default()),
+        })
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn get(&self, url: &Url) -> Result<Response> {
+        let mut req = self.client.get(url.clone());
+        if let Some(cookie) = self.cookies.lock().unwrap().to_header() {
+            req.header(cookie);
+        }
+
+        let mut res = req.send()?;
+        if let Some(set_cookie) = res.headers().get::<header::SetCookie>() {
+            self.cookies.lock().unwrap().store(set_cookie);
+        }
+
+        Ok(Response {
+            status: res.status().as_u16(),
+            body: res.text()?,
+        })
+    }
+}
+
+/// `Transport` serving pages in memory, keyed by URL, without network.
+/// Requests to unknown URLs get an empty response of status 404.
+///
+/// # Example
+///
+/// ```
+/// extern crate reqwest;
+/// extern crate scholar;
+///
+/// use reqwest::Url;
+/// use scholar::transport::{MemoryTransport, Response, Transport};
+///
+/// # fn main() {
+/// let url = Url::parse("https://scholar.google.com/scholar?q=foo").unwrap();
+///
+/// let mut transport = MemoryTransport::new();
+/// transport.insert(&url, Response::ok("<html></html>"));
+///
+/// assert_eq!(transport.get(&url).unwrap().body, "<html></html>");
+/// assert_eq!(transport.requested_urls(), vec![url.to_string()]);
+/// # }
+/// ```
+#[derive(Default)]
+pub struct MemoryTransport {
+    pages: BTreeMap<String, Vec<Response>>,
+    requested_urls: Mutex<Vec<String>>,
+}
+
+impl MemoryTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve `response` for `url`.
+    /// If several responses are inserted for one URL, they are served in order,
+    /// and the last one is repeated.
+    pub fn insert(&mut self, url: &Url, response: Response) {
+        self.pages
+            .entry(url.to_string())
+            .or_default()
+            .push(response);
+    }
+
+    /// Serve the content of a file at `path` for `url` with status 200,
+    /// e.g. a page saved in a fixture directory.
+    pub fn insert_file<P: AsRef<Path>>(&mut self, url: &Url, path: P) -> Result<()> {
+        let mut body = String::new();
+        File::open(path)?.read_to_string(&mut body)?;
+        self.insert(url, Response { status: 200, body });
+        Ok(())
+    }
+
+    /// URLs requested so far, in order.
+    pub fn requested_urls(&self) -> Vec<String> {
+        self.requested_urls.lock().unwrap().clone()
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn get(&self, url: &Url) -> Result<Response> {
+        let url = url.to_string();
+
+        let served_count = {
+            let mut requested_urls = self.requested_urls.lock().unwrap();
+            let count = requested_urls.iter().filter(|u| **u == url).count();
+            requested_urls.push(url.clone());
+            count
+        };
+
+        let response = match self.pages.get(&url) {
+            Some(responses) => {
+                let i = cmp::min(served_count, responses.len() - 1);
+                responses[i].clone()
+            }
+            None => Response {
+                status: 404,
+                body: String::new(),
+            },
+        };
+
+        Ok(response)
+    }
+}
+
+// Cookies set by Google Scholar, sent back with the following requests.
+#[derive(Default)]
+struct CookieJar(BTreeMap<String, String>);
+
+impl CookieJar {
+    fn store(&mut self, set_cookie: &header::SetCookie) {
+        for (name, value) in set_cookie.iter().filter_map(|c| parse_set_cookie(c)) {
+            if value.is_empty() {
+                self.0.remove(&name);
+            } else {
+                self.0.insert(name, value);
+            }
+        }
+    }
+
+    fn to_header(&self) -> Option<header::Cookie> {
+        if self.0.is_empty() {
+            return None;
+        }
+
+        let mut cookie = header::Cookie::new();
+        for (name, value) in &self.0 {
+            cookie.set(name.clone(), value.clone());
+        }
+        Some(cookie)
+    }
+}
+
+// "NAME=VALUE; expires=...; path=/; domain=..." -> (NAME, VALUE)
+// Attributes are ignored, since cookies are sent only to Google Scholar.
+fn parse_set_cookie(set_cookie: &str) -> Option<(String, String)> {
+    let pair = set_cookie.split(';').next()?;
+    let eq_pos = pair.find('=')?;
+    let name = pair[..eq_pos].trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    Some((name.to_owned(), pair[eq_pos + 1..].trim().to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_set_cookie_test() {
+        assert_eq!(
+            parse_set_cookie("GSP=ID=0123abcd:CPTS=1520000000; expires=Mon, 09-Mar-2020; path=/"),
+            Some((
+                String::from("GSP"),
+                String::from("ID=0123abcd:CPTS=1520000000")
+            ))
+        );
+        assert_eq!(
+            parse_set_cookie("NID=; path=/"),
+            Some((String::from("NID"), String::new()))
+        );
+        assert_eq!(parse_set_cookie("foo"), None);
+        assert_eq!(parse_set_cookie("=bar"), None);
+    }
+
+    #[test]
+    fn cookie_jar_test() {
+        let mut jar = CookieJar::default();
+        assert!(jar.to_header().is_none());
+
+        jar.store(&header::SetCookie(vec![
+            String::from("GSP=foo; path=/"),
+            String::from("NID=bar; path=/"),
+        ]));
+        jar.store(&header::SetCookie(vec![
+            String::from("GSP=baz"),
+            String::from("NID="),
+        ]));
+
+        let cookie = jar.to_header().unwrap();
+        assert_eq!(cookie.get("GSP"), Some("baz"));
+        assert_eq!(cookie.get("NID"), None);
+    }
+
+    #[test]
+    fn memory_transport_test() {
+        let url = Url::parse("https://scholar.google.com/scholar?q=foo").unwrap();
+        let unknown_url = Url::parse("https://scholar.google.com/scholar?q=bar").unwrap();
+
+        let mut transport = MemoryTransport::new();
+        transport.insert(
+            &url,
+            Response {
+                status: 503,
+                body: String::new(),
+            },
+        );
+        transport.insert(&url, Response::ok("foo"));
+
+        assert_eq!(transport.get(&url).unwrap().status, 503);
+        assert_eq!(transport.get(&url).unwrap(), Response::ok("foo"));
+        assert_eq!(transport.get(&url).unwrap(), Response::ok("foo"));
+        assert_eq!(transport.get(&unknown_url).unwrap().status, 404);
+        assert_eq!(transport.requested_urls().len(), 4);
+    }
+}